@@ -0,0 +1,289 @@
+use std::f32::consts::TAU;
+
+use super::{
+    collider::Rectangle,
+    grid::{screen_translation::clamp_tile_range, Position, Projection, RectSize, ScreenTranslation},
+};
+use crate::ScreenView;
+
+/// rotations closer than this to a full turn count as axis aligned, so accumulated float errors don't matter
+const AXIS_ALIGNED_TOLERANCE: f32 = 1e-4;
+
+/// screen shake in progress, see `Camera2D::shake`
+#[derive(Debug, Clone, Copy)]
+struct Shake {
+    intensity: f32,
+    duration: f32,
+    elapsed: f32,
+}
+
+/// 2D camera looking at a world position, drawing it into a screen view.
+///
+/// world and screen coordinates share their axes, y grows downwards.
+/// the zoom is the amount of screen pixels per world unit. the rotation is given in radians,
+/// rotating the camera clockwise turns the world counter clockwise on the screen.
+///
+/// # Examples
+/// ```
+/// let mut camera = Camera2D::new(screen_view);
+/// camera.set_world_bounds(Some(Rectangle { left: 0., top: 0., right: map_width, bottom: map_height }));
+///
+/// // every frame
+/// camera.follow(player.x, player.y, 5., time.delta);
+/// camera.update(time.delta);
+/// let (x, y) = camera.world_to_screen(enemy.x, enemy.y);
+/// let (cursor_x, cursor_y) = camera.screen_to_world(mouse_x, mouse_y);
+///
+/// // on hit
+/// camera.shake(8., 0.3);
+/// ```
+#[derive(Clone, Copy)]
+pub struct Camera2D {
+    screen_view: ScreenView,
+    center_x: f32,
+    center_y: f32,
+    zoom: f32,
+    rotation: f32,
+    world_bounds: Option<Rectangle>,
+    shake: Option<Shake>,
+}
+
+impl Camera2D {
+    /// camera looking at the world origin without zoom or rotation
+    pub fn new(screen_view: ScreenView) -> Self {
+        Self {
+            screen_view,
+            center_x: 0.,
+            center_y: 0.,
+            zoom: 1.,
+            rotation: 0.,
+            world_bounds: None,
+            shake: None,
+        }
+    }
+
+    pub const fn screen_view(&self) -> ScreenView {
+        self.screen_view
+    }
+
+    /// e.g. after the window has been resized
+    pub fn set_screen_view(&mut self, screen_view: ScreenView) {
+        self.screen_view = screen_view;
+        self.clamp_to_world_bounds();
+    }
+
+    /// world position shown in the middle of the screen view
+    pub const fn center(&self) -> (f32, f32) {
+        (self.center_x, self.center_y)
+    }
+
+    pub fn set_center(&mut self, center_x: f32, center_y: f32) {
+        self.center_x = center_x;
+        self.center_y = center_y;
+        self.clamp_to_world_bounds();
+    }
+
+    pub const fn zoom(&self) -> f32 {
+        self.zoom
+    }
+
+    /// # Panics
+    /// if `zoom` is not positive
+    pub fn set_zoom(&mut self, zoom: f32) {
+        assert!(zoom > 0., "the zoom has to be positive");
+        self.zoom = zoom;
+        self.clamp_to_world_bounds();
+    }
+
+    /// multiplies the zoom by `factor` while the world position below the screen point stays in place, e.g. below the cursor
+    pub fn zoom_at(&mut self, factor: f32, screen_x: f32, screen_y: f32) {
+        let (world_x, world_y) = self.screen_to_world(screen_x, screen_y);
+        self.set_zoom(self.zoom * factor);
+        let (moved_x, moved_y) = self.screen_to_world(screen_x, screen_y);
+        self.set_center(self.center_x + world_x - moved_x, self.center_y + world_y - moved_y);
+    }
+
+    pub const fn rotation(&self) -> f32 {
+        self.rotation
+    }
+
+    pub fn set_rotation(&mut self, rotation: f32) {
+        self.rotation = rotation;
+        self.clamp_to_world_bounds();
+    }
+
+    /// moves the camera by a distance in screen pixels, e.g. while dragging the view
+    pub fn pan(&mut self, screen_dx: f32, screen_dy: f32) {
+        let (dx, dy) = rotate(screen_dx / self.zoom, screen_dy / self.zoom, self.rotation);
+        self.set_center(self.center_x + dx, self.center_y + dy);
+    }
+
+    pub const fn world_bounds(&self) -> Option<Rectangle> {
+        self.world_bounds
+    }
+
+    /// area the camera may show, if the visible area is larger than the bounds it is centered on them
+    pub fn set_world_bounds(&mut self, world_bounds: Option<Rectangle>) {
+        self.world_bounds = world_bounds;
+        self.clamp_to_world_bounds();
+    }
+
+    /// moves the center the fraction `t` of the way towards the target
+    pub fn lerp_towards(&mut self, target_x: f32, target_y: f32, t: f32) {
+        self.set_center(
+            self.center_x + (target_x - self.center_x) * t,
+            self.center_y + (target_y - self.center_y) * t,
+        );
+    }
+
+    /// smoothly moves the center towards the target independent of the frame rate,
+    /// a higher `sharpness` catches up faster
+    pub fn follow(&mut self, target_x: f32, target_y: f32, sharpness: f32, delta_time: f32) {
+        self.lerp_towards(target_x, target_y, 1. - (-sharpness * delta_time).exp());
+    }
+
+    /// shakes the screen by up to `intensity` pixels, fading out over `duration` seconds.
+    /// replaces a shake that is still in progress
+    pub fn shake(&mut self, intensity: f32, duration: f32) {
+        self.shake = (duration > 0.).then_some(Shake {
+            intensity,
+            duration,
+            elapsed: 0.,
+        });
+    }
+
+    /// advances time based effects like the screen shake
+    pub fn update(&mut self, delta_time: f32) {
+        if let Some(shake) = &mut self.shake {
+            shake.elapsed += delta_time;
+            if shake.elapsed >= shake.duration {
+                self.shake = None;
+            }
+        }
+    }
+
+    /// current offset of the screen shake in pixels
+    pub fn shake_offset(&self) -> (f32, f32) {
+        let Some(shake) = self.shake else {
+            return (0., 0.);
+        };
+
+        let strength = shake.intensity * (1. - shake.elapsed / shake.duration);
+        let t = shake.elapsed;
+        // overlapping waves of unrelated frequencies look random without needing a random source
+        let x = 0.6 * (t * 47.3).sin() + 0.4 * (t * 29.1 + 1.7).sin();
+        let y = 0.6 * (t * 41.9 + 0.5).sin() + 0.4 * (t * 23.7 + 2.9).sin();
+        (x * strength, y * strength)
+    }
+
+    pub fn world_to_screen(&self, world_x: f32, world_y: f32) -> (f32, f32) {
+        let (x, y) = rotate(world_x - self.center_x, world_y - self.center_y, -self.rotation);
+        let (screen_center_x, screen_center_y) = self.screen_center();
+        (screen_center_x + x * self.zoom, screen_center_y + y * self.zoom)
+    }
+
+    pub fn screen_to_world(&self, screen_x: f32, screen_y: f32) -> (f32, f32) {
+        let (screen_center_x, screen_center_y) = self.screen_center();
+        let (x, y) = rotate((screen_x - screen_center_x) / self.zoom, (screen_y - screen_center_y) / self.zoom, self.rotation);
+        (self.center_x + x, self.center_y + y)
+    }
+
+    /// axis aligned world area covering everything inside of the screen view
+    pub fn visible_world_area(&self) -> Rectangle {
+        let view = self.screen_view;
+        let corners = [
+            (view.offset_x, view.offset_y),
+            (view.offset_x + view.width, view.offset_y),
+            (view.offset_x, view.offset_y + view.height),
+            (view.offset_x + view.width, view.offset_y + view.height),
+        ]
+        .map(|(x, y)| self.screen_to_world(x, y));
+
+        corners.iter().fold(
+            Rectangle {
+                left: f32::INFINITY,
+                top: f32::INFINITY,
+                right: f32::NEG_INFINITY,
+                bottom: f32::NEG_INFINITY,
+            },
+            |area, &(x, y)| Rectangle {
+                left: area.left.min(x),
+                top: area.top.min(y),
+                right: area.right.max(x),
+                bottom: area.bottom.max(y),
+            },
+        )
+    }
+
+    /// translation for a grid placed in the world with its top left corner at the world origin,
+    /// each tile being `tile_width` world units wide.
+    ///
+    /// the grid keeps its position when the camera moves, so the result has to be created again after every change.
+    /// it is clipped to the screen view, so screen points outside of it don't hit any tile.
+    /// `None` while the camera is rotated by anything but whole turns, screen translations are axis aligned.
+    pub fn screen_translation(&self, grid_size: RectSize, projection: Projection, tile_width: f32) -> Option<ScreenTranslation> {
+        let angle = self.rotation.rem_euclid(TAU);
+        if angle.min(TAU - angle) > AXIS_ALIGNED_TOLERANCE {
+            return None;
+        }
+
+        let (grid_width, grid_height) = projection.grid_extent(grid_size);
+        let (left, top) = self.world_to_screen(0., 0.);
+        let screen_view = ScreenView {
+            offset_x: left,
+            offset_y: top,
+            width: grid_width * tile_width * self.zoom,
+            height: grid_height * tile_width * self.zoom,
+        };
+        Some(ScreenTranslation::with_projection(screen_view, grid_size, projection).clipped_to(self.screen_view))
+    }
+
+    /// top left position and size of the tiles of an orthogonal grid that intersect the screen view, grown by `margin` tiles
+    /// and clamped to the grid. the grid is placed like in `screen_translation`, see `ScreenTranslation::visible_range`.
+    ///
+    /// works while the camera is rotated as well, the range then covers the bounding box of the visible area.
+    pub fn visible_tile_range(&self, grid_size: RectSize, tile_width: f32, margin: usize) -> Option<(Position, RectSize)> {
+        let area = self.visible_world_area();
+        let first = Position::new((area.left / tile_width).floor() as i64, (area.top / tile_width).floor() as i64);
+        let last = Position::new((area.right / tile_width).ceil() as i64 - 1, (area.bottom / tile_width).ceil() as i64 - 1);
+        clamp_tile_range(first, last, margin, grid_size)
+    }
+
+    /// center of the screen view including the screen shake
+    fn screen_center(&self) -> (f32, f32) {
+        let (shake_x, shake_y) = self.shake_offset();
+        (
+            self.screen_view.offset_x + self.screen_view.width / 2. + shake_x,
+            self.screen_view.offset_y + self.screen_view.height / 2. + shake_y,
+        )
+    }
+
+    fn clamp_to_world_bounds(&mut self) {
+        let Some(bounds) = self.world_bounds else {
+            return;
+        };
+
+        // half of the visible world area, which grows while the camera is rotated
+        let (sin, cos) = self.rotation.sin_cos();
+        let (width, height) = (self.screen_view.width / self.zoom, self.screen_view.height / self.zoom);
+        let half_width = (cos.abs() * width + sin.abs() * height) / 2.;
+        let half_height = (sin.abs() * width + cos.abs() * height) / 2.;
+
+        self.center_x = clamp_axis(self.center_x, bounds.left, bounds.right, half_width);
+        self.center_y = clamp_axis(self.center_y, bounds.top, bounds.bottom, half_height);
+    }
+}
+
+/// rotates the vector clockwise on the screen
+fn rotate(x: f32, y: f32, angle: f32) -> (f32, f32) {
+    let (sin, cos) = angle.sin_cos();
+    (x * cos - y * sin, x * sin + y * cos)
+}
+
+fn clamp_axis(center: f32, min: f32, max: f32, half_extent: f32) -> f32 {
+    if max - min <= 2. * half_extent {
+        (min + max) / 2.
+    } else {
+        center.clamp(min + half_extent, max - half_extent)
+    }
+}
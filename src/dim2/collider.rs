@@ -17,6 +17,7 @@ impl RelativeRectangle {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Rectangle {
     pub left: f32,
     pub top: f32,
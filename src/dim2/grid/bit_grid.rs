@@ -0,0 +1,322 @@
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+
+use super::{patterns::PatternPositions, BoundedGrid, Grid, GridError, GridRead, Position, RectSize};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// grid of booleans packed into `u64` words, suited for large walkability or visibility masks.
+///
+/// every row starts at a new word, the bits beyond the width are always cleared.
+/// masks of the same size can be combined with `&`, `|`, `^` and `!`.
+///
+/// # Examples
+/// ```
+/// let walkable = BitGrid::from_grid(&map, &|tile| tile.is_walkable());
+/// let mut visible = BitGrid::new(map.width(), map.height(), false);
+/// visible.set_pattern(player.position, square_pattern(8).as_ref(), true);
+///
+/// let reachable_and_visible = &walkable & &visible;
+/// for position in reachable_and_visible.iter_set_positions() {
+///     highlight(position);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    size: RectSize,
+    words_per_row: usize,
+    words: Box<[u64]>,
+}
+
+impl BitGrid {
+    pub fn new(width: usize, height: usize, initializer_value: bool) -> Self {
+        let words_per_row = width.div_ceil(WORD_BITS);
+        let mut grid = Self {
+            size: RectSize::new(width, height),
+            words_per_row,
+            words: vec![0; words_per_row * height].into_boxed_slice(),
+        };
+        grid.fill(initializer_value);
+        grid
+    }
+
+    /// mask of all tiles of `grid` for which `is_set` holds
+    pub fn from_grid<G: BoundedGrid + ?Sized>(grid: &G, is_set: &dyn Fn(&G::Tile) -> bool) -> Self {
+        let size = grid.size();
+        let mut result = Self::new(size.width, size.height, false);
+        for position in size.iter() {
+            if grid.get_ref(position).is_some_and(is_set) {
+                result.set_bit(position);
+            }
+        }
+        result
+    }
+
+    pub const fn size(&self) -> RectSize {
+        self.size
+    }
+
+    pub const fn width(&self) -> usize {
+        self.size.width
+    }
+
+    pub const fn height(&self) -> usize {
+        self.size.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.size.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size.is_empty()
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        position.x >= 0 && position.y >= 0 && (position.x as usize) < self.size.width && (position.y as usize) < self.size.height
+    }
+
+    pub fn get(&self, position: Position) -> Option<bool> {
+        self.contains(position).then(|| {
+            let (word, bit) = self.word_index(position);
+            self.words[word] & bit != 0
+        })
+    }
+
+    pub fn set(&mut self, position: Position, value: bool) -> Result<(), GridError> {
+        if !self.contains(position) {
+            return Err(GridError::OutOfBounds { position, size: self.size });
+        }
+
+        let (word, bit) = self.word_index(position);
+        if value {
+            self.words[word] |= bit;
+        } else {
+            self.words[word] &= !bit;
+        }
+        Ok(())
+    }
+
+    /// sets all pattern positions around `center` to `value`, positions outside of the grid are skipped
+    pub fn set_pattern(&mut self, center: Position, pattern: &dyn PatternPositions, value: bool) {
+        for position in pattern.iter_pattern_positions(center, true) {
+            let _ = self.set(position, value);
+        }
+    }
+
+    pub fn fill(&mut self, value: bool) {
+        self.words.fill(if value { u64::MAX } else { 0 });
+        self.clear_padding();
+    }
+
+    /// amount of set tiles
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    /// whether any tile is set
+    pub fn any(&self) -> bool {
+        self.words.iter().any(|&word| word != 0)
+    }
+
+    /// copy of the mask moved by `offset`, tiles moved beyond the edges are dropped and uncovered tiles are cleared
+    pub fn shifted(&self, offset: Position) -> BitGrid {
+        let mut result = Self::new(self.width(), self.height(), false);
+        // every tile is moved beyond the edges, which also keeps the arithmetic below from overflowing
+        if offset.x.unsigned_abs() >= self.width() as u64 || offset.y.unsigned_abs() >= self.height() as u64 {
+            return result;
+        }
+
+        for y in 0..self.height() {
+            let source_y = y as i64 - offset.y;
+            if source_y < 0 || source_y >= self.height() as i64 {
+                continue;
+            }
+            let source = self.row_words(source_y as usize);
+            let target = &mut result.words[y * self.words_per_row..(y + 1) * self.words_per_row];
+            shift_row(source, target, offset.x);
+        }
+        result.clear_padding();
+        result
+    }
+
+    /// positions of all set tiles in row major order
+    pub fn iter_set_positions(&self) -> impl Iterator<Item = Position> + '_ {
+        self.words.iter().enumerate().flat_map(move |(index, &word)| {
+            let origin = Position::new(((index % self.words_per_row) * WORD_BITS) as i64, (index / self.words_per_row) as i64);
+            SetBits(word).map(move |bit| Position::new(origin.x + bit as i64, origin.y))
+        })
+    }
+
+    pub fn to_grid(&self) -> Grid<bool> {
+        let values: Vec<_> = self.size.iter().map(|position| self.get(position) == Some(true)).collect();
+        Grid::with_preset_values(self.width(), self.height(), values.into_boxed_slice())
+    }
+
+    fn word_index(&self, position: Position) -> (usize, u64) {
+        let x = position.x as usize;
+        (position.y as usize * self.words_per_row + x / WORD_BITS, 1 << (x % WORD_BITS))
+    }
+
+    fn set_bit(&mut self, position: Position) {
+        let (word, bit) = self.word_index(position);
+        self.words[word] |= bit;
+    }
+
+    fn row_words(&self, y: usize) -> &[u64] {
+        &self.words[y * self.words_per_row..(y + 1) * self.words_per_row]
+    }
+
+    /// restores the invariant that bits beyond the width are cleared
+    fn clear_padding(&mut self) {
+        let used_bits = self.size.width % WORD_BITS;
+        if used_bits == 0 {
+            return;
+        }
+
+        let mask = (1 << used_bits) - 1;
+        for row in self.words.chunks_exact_mut(self.words_per_row) {
+            if let Some(last) = row.last_mut() {
+                *last &= mask;
+            }
+        }
+    }
+
+    fn combine(&mut self, other: &BitGrid, operation: fn(u64, u64) -> u64) {
+        assert_eq!(self.size, other.size, "bit grids of different sizes can't be combined");
+        for (word, &other_word) in self.words.iter_mut().zip(other.words.iter()) {
+            *word = operation(*word, other_word);
+        }
+    }
+}
+
+/// moves the bits of `source` by `shift` towards higher x into `target`, both being rows of the same length
+fn shift_row(source: &[u64], target: &mut [u64], shift: i64) {
+    let word_shift = (shift.unsigned_abs() / WORD_BITS as u64) as usize;
+    let bit_shift = (shift.unsigned_abs() % WORD_BITS as u64) as u32;
+    let word = |index: Option<usize>| index.and_then(|index| source.get(index)).copied().unwrap_or(0);
+
+    for (index, target_word) in target.iter_mut().enumerate() {
+        *target_word = if shift >= 0 {
+            let carry = if bit_shift == 0 {
+                0
+            } else {
+                word(index.checked_sub(word_shift + 1)) >> (WORD_BITS as u32 - bit_shift)
+            };
+            (word(index.checked_sub(word_shift)) << bit_shift) | carry
+        } else {
+            let carry = if bit_shift == 0 {
+                0
+            } else {
+                word(index.checked_add(word_shift + 1)) << (WORD_BITS as u32 - bit_shift)
+            };
+            (word(index.checked_add(word_shift)) >> bit_shift) | carry
+        };
+    }
+}
+
+/// indices of the set bits of a word, from the lowest to the highest
+struct SetBits(u64);
+
+impl Iterator for SetBits {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.0 == 0 {
+            return None;
+        }
+        let bit = self.0.trailing_zeros();
+        self.0 &= self.0 - 1;
+        Some(bit)
+    }
+}
+
+impl From<&Grid<bool>> for BitGrid {
+    fn from(grid: &Grid<bool>) -> Self {
+        Self::from_grid(grid, &|&value| value)
+    }
+}
+
+impl GridRead for BitGrid {
+    type Tile = bool;
+
+    fn bounds(&self) -> Option<RectSize> {
+        Some(self.size)
+    }
+
+    fn get_ref(&self, position: Position) -> Option<&bool> {
+        self.get(position).map(|value| if value { &true } else { &false })
+    }
+}
+
+impl BoundedGrid for BitGrid {
+    fn size(&self) -> RectSize {
+        self.size
+    }
+}
+
+/// # Panics
+/// if the sizes of both grids differ
+impl BitAndAssign<&BitGrid> for BitGrid {
+    fn bitand_assign(&mut self, other: &BitGrid) {
+        self.combine(other, |a, b| a & b);
+    }
+}
+
+/// # Panics
+/// if the sizes of both grids differ
+impl BitOrAssign<&BitGrid> for BitGrid {
+    fn bitor_assign(&mut self, other: &BitGrid) {
+        self.combine(other, |a, b| a | b);
+    }
+}
+
+/// # Panics
+/// if the sizes of both grids differ
+impl BitXorAssign<&BitGrid> for BitGrid {
+    fn bitxor_assign(&mut self, other: &BitGrid) {
+        self.combine(other, |a, b| a ^ b);
+    }
+}
+
+impl BitAnd for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitand(self, other: &BitGrid) -> BitGrid {
+        let mut result = self.clone();
+        result &= other;
+        result
+    }
+}
+
+impl BitOr for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitor(self, other: &BitGrid) -> BitGrid {
+        let mut result = self.clone();
+        result |= other;
+        result
+    }
+}
+
+impl BitXor for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitxor(self, other: &BitGrid) -> BitGrid {
+        let mut result = self.clone();
+        result ^= other;
+        result
+    }
+}
+
+impl Not for &BitGrid {
+    type Output = BitGrid;
+
+    fn not(self) -> BitGrid {
+        let mut result = self.clone();
+        for word in result.words.iter_mut() {
+            *word = !*word;
+        }
+        result.clear_padding();
+        result
+    }
+}
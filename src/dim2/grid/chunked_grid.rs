@@ -0,0 +1,222 @@
+use std::collections::{hash_map::Entry, HashMap};
+
+use super::{
+    patterns::{get_grid_values_from_pattern, PatternPositions},
+    Grid, GridError, GridRead, GridWrite, Position, RectSize,
+};
+
+/// creates the content of a chunk from its chunk position
+pub type ChunkFactory<T> = Box<dyn Fn(Position) -> Grid<T>>;
+
+/// receives every unloaded chunk together with its chunk position
+pub type UnloadCallback<T> = Box<dyn FnMut(Position, Grid<T>)>;
+
+/// unbounded grid made of fixed size chunks that are only allocated once they are written to.
+///
+/// positions may be negative, chunk `(0, 0)` covers the positions from `(0, 0)` up to the chunk size.
+/// tiles of chunks that are not loaded read as `None`, writing to them loads the chunk first.
+///
+/// # Examples
+/// ```
+/// let mut world = ChunkedGrid::with_chunk_factory(RectSize::new(32, 32), Box::new(|chunk| generate_terrain(chunk)));
+/// world.set_unload_callback(Box::new(|chunk, tiles| save_chunk(chunk, tiles)));
+///
+/// world.set(Position::new(-100, 2000), Tile::Torch).unwrap();
+/// world.unload_chunks(&|chunk, _| chunk.x.abs_diff(player_chunk.x) > 4 || chunk.y.abs_diff(player_chunk.y) > 4);
+/// ```
+pub struct ChunkedGrid<T> {
+    chunk_size: RectSize,
+    chunks: HashMap<Position, Grid<T>>,
+    chunk_factory: ChunkFactory<T>,
+    unload_callback: Option<UnloadCallback<T>>,
+}
+
+impl<T> ChunkedGrid<T> {
+    /// new chunks are filled with `initializer_value`
+    pub fn new(chunk_size: RectSize, initializer_value: T) -> Self
+    where
+        T: Clone + 'static,
+    {
+        Self::with_chunk_factory(
+            chunk_size,
+            Box::new(move |_| Grid::new(chunk_size.width, chunk_size.height, initializer_value.clone())),
+        )
+    }
+
+    /// new chunks are created by `chunk_factory`, which receives the chunk position
+    /// and has to return a grid of `chunk_size`
+    pub fn with_chunk_factory(chunk_size: RectSize, chunk_factory: ChunkFactory<T>) -> Self {
+        assert!(!chunk_size.is_empty(), "chunks need at least one tile");
+        Self {
+            chunk_size,
+            chunks: HashMap::new(),
+            chunk_factory,
+            unload_callback: None,
+        }
+    }
+
+    /// called with every chunk that is unloaded, e.g. to persist it.
+    /// chunks that are still loaded when the grid is dropped are not passed to it, see `unload_all`
+    pub fn set_unload_callback(&mut self, unload_callback: UnloadCallback<T>) {
+        self.unload_callback = Some(unload_callback);
+    }
+
+    pub const fn chunk_size(&self) -> RectSize {
+        self.chunk_size
+    }
+
+    /// position of the chunk that contains `position`
+    pub fn chunk_position(&self, position: Position) -> Position {
+        Position::new(
+            position.x.div_euclid(self.chunk_size.width as i64),
+            position.y.div_euclid(self.chunk_size.height as i64),
+        )
+    }
+
+    /// position of `position` inside of its chunk
+    pub fn local_position(&self, position: Position) -> Position {
+        Position::new(
+            position.x.rem_euclid(self.chunk_size.width as i64),
+            position.y.rem_euclid(self.chunk_size.height as i64),
+        )
+    }
+
+    /// position of the top left tile of `chunk`
+    pub fn chunk_origin(&self, chunk: Position) -> Position {
+        Position::new(chunk.x * self.chunk_size.width as i64, chunk.y * self.chunk_size.height as i64)
+    }
+
+    /// amount of loaded chunks
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    pub fn is_chunk_loaded(&self, chunk: Position) -> bool {
+        self.chunks.contains_key(&chunk)
+    }
+
+    pub fn chunk(&self, chunk: Position) -> Option<&Grid<T>> {
+        self.chunks.get(&chunk)
+    }
+
+    pub fn chunk_mut(&mut self, chunk: Position) -> Option<&mut Grid<T>> {
+        self.chunks.get_mut(&chunk)
+    }
+
+    /// the chunk at `chunk`, creating it if it isn't loaded yet.
+    /// fails if the chunk factory creates a grid that doesn't have the chunk size, which is not loaded then
+    pub fn load_chunk(&mut self, chunk: Position) -> Result<&mut Grid<T>, GridError> {
+        match self.chunks.entry(chunk) {
+            Entry::Occupied(entry) => Ok(entry.into_mut()),
+            Entry::Vacant(entry) => {
+                let grid = (self.chunk_factory)(chunk);
+                if grid.size() != self.chunk_size {
+                    return Err(GridError::DimensionMismatch {
+                        expected: self.chunk_size,
+                        actual: grid.size(),
+                    });
+                }
+                Ok(entry.insert(grid))
+            }
+        }
+    }
+
+    /// removes the chunk and passes it to the unload callback, returns whether the chunk has been loaded
+    pub fn unload_chunk(&mut self, chunk: Position) -> bool {
+        let Some(grid) = self.chunks.remove(&chunk) else {
+            return false;
+        };
+        if let Some(unload_callback) = &mut self.unload_callback {
+            unload_callback(chunk, grid);
+        }
+        true
+    }
+
+    /// unloads every chunk for which `should_unload` holds, returns the amount of unloaded chunks
+    pub fn unload_chunks(&mut self, should_unload: &dyn Fn(Position, &Grid<T>) -> bool) -> usize {
+        let chunks: Vec<_> = self
+            .chunks
+            .iter()
+            .filter(|(&chunk, grid)| should_unload(chunk, grid))
+            .map(|(&chunk, _)| chunk)
+            .collect();
+        for &chunk in &chunks {
+            self.unload_chunk(chunk);
+        }
+        chunks.len()
+    }
+
+    pub fn unload_all(&mut self) {
+        self.unload_chunks(&|_, _| true);
+    }
+
+    pub fn get(&self, position: Position) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.get_ref(position).cloned()
+    }
+
+    pub fn get_ref(&self, position: Position) -> Option<&T> {
+        self.chunks.get(&self.chunk_position(position))?.get_ref(self.local_position(position))
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        let local_position = self.local_position(position);
+        self.chunks.get_mut(&self.chunk_position(position))?.get_mut(local_position)
+    }
+
+    /// sets the tile at `position`, loading its chunk if necessary, which fails like `load_chunk`
+    pub fn set(&mut self, position: Position, value: T) -> Result<(), GridError> {
+        let local_position = self.local_position(position);
+        let chunk = self.load_chunk(self.chunk_position(position))?;
+        chunk.set(local_position, value)
+    }
+
+    /// values of all pattern positions around `center` in loaded chunks, same as `get_grid_values_from_pattern`
+    pub fn get_values_from_pattern(&self, center: Position, fill_center: bool, pattern: &dyn PatternPositions) -> Box<[T]>
+    where
+        T: Clone,
+    {
+        get_grid_values_from_pattern(self, center, fill_center, pattern)
+    }
+
+    /// all loaded chunks with their chunk position, in no particular order
+    pub fn chunks(&self) -> impl Iterator<Item = (Position, &Grid<T>)> {
+        self.chunks.iter().map(|(&chunk, grid)| (chunk, grid))
+    }
+
+    pub fn chunks_mut(&mut self) -> impl Iterator<Item = (Position, &mut Grid<T>)> {
+        self.chunks.iter_mut().map(|(&chunk, grid)| (chunk, grid))
+    }
+
+    /// every tile of the loaded chunks with its world position, chunk by chunk in no particular order
+    pub fn iter_with_position(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.chunks.iter().flat_map(move |(&chunk, grid)| {
+            let origin = self.chunk_origin(chunk);
+            grid.iter_with_position().map(move |(position, value)| (origin + position, value))
+        })
+    }
+}
+
+impl<T> GridRead for ChunkedGrid<T> {
+    type Tile = T;
+
+    fn bounds(&self) -> Option<RectSize> {
+        None
+    }
+
+    fn get_ref(&self, position: Position) -> Option<&T> {
+        ChunkedGrid::get_ref(self, position)
+    }
+}
+
+impl<T> GridWrite for ChunkedGrid<T> {
+    fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        ChunkedGrid::get_mut(self, position)
+    }
+
+    fn set(&mut self, position: Position, value: T) -> Result<(), GridError> {
+        ChunkedGrid::set(self, position, value)
+    }
+}
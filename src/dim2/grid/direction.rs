@@ -0,0 +1,84 @@
+use super::{Position, Rotation};
+
+/// one of the 8 directions pointing to an adjacent tile.
+/// `y` grows downwards, matching the screen translation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    /// all directions in clockwise order, starting with `Up`
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    pub const ORTHOGONAL: [Direction; 4] = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+
+    pub const DIAGONAL: [Direction; 4] = [Direction::UpRight, Direction::DownRight, Direction::DownLeft, Direction::UpLeft];
+
+    /// offset to the adjacent tile in this direction
+    pub const fn offset(&self) -> Position {
+        match self {
+            Direction::Up => Position::new(0, -1),
+            Direction::UpRight => Position::new(1, -1),
+            Direction::Right => Position::new(1, 0),
+            Direction::DownRight => Position::new(1, 1),
+            Direction::Down => Position::new(0, 1),
+            Direction::DownLeft => Position::new(-1, 1),
+            Direction::Left => Position::new(-1, 0),
+            Direction::UpLeft => Position::new(-1, -1),
+        }
+    }
+
+    /// the direction pointing to `offset`, which has to be the offset of an adjacent tile
+    pub fn from_offset(offset: Position) -> Option<Direction> {
+        Self::ALL.into_iter().find(|direction| direction.offset() == offset)
+    }
+
+    pub const fn opposite(&self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::UpRight => Direction::DownLeft,
+            Direction::Right => Direction::Left,
+            Direction::DownRight => Direction::UpLeft,
+            Direction::Down => Direction::Up,
+            Direction::DownLeft => Direction::UpRight,
+            Direction::Left => Direction::Right,
+            Direction::UpLeft => Direction::DownRight,
+        }
+    }
+
+    pub const fn is_diagonal(&self) -> bool {
+        matches!(self, Direction::UpRight | Direction::DownRight | Direction::DownLeft | Direction::UpLeft)
+    }
+
+    /// the direction after turning clockwise by `rotation`
+    pub const fn rotated(&self, rotation: Rotation) -> Direction {
+        let index = match self {
+            Direction::Up => 0,
+            Direction::UpRight => 1,
+            Direction::Right => 2,
+            Direction::DownRight => 3,
+            Direction::Down => 4,
+            Direction::DownLeft => 5,
+            Direction::Left => 6,
+            Direction::UpLeft => 7,
+        };
+        Direction::ALL[(index + 2 * rotation.quarter_turns()) % 8]
+    }
+}
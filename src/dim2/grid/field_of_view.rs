@@ -0,0 +1,183 @@
+use std::collections::HashSet;
+
+use super::{BoundedGrid, Grid, GridRead, Position};
+
+/// visibility mask of every tile seen from `origin` within `radius`, using symmetric shadowcasting.
+///
+/// opaque tiles block the sight, but are visible themselves. tiles outside of the grid are treated as opaque.
+/// if a floor tile `b` is visible from `a`, `a` is visible from `b` as well.
+/// `get_visible_positions` works on unbounded grids as well.
+///
+/// # Examples
+/// ```
+/// let visible = get_field_of_view(&map, player.position, 8, &|tile: &Tile| *tile == Tile::Wall);
+/// for (position, explored) in fog_of_war.iter_mut_with_position() {
+///     *explored |= visible[position];
+/// }
+/// ```
+pub fn get_field_of_view<G: BoundedGrid + ?Sized>(grid: &G, origin: Position, radius: usize, is_opaque: &dyn Fn(&G::Tile) -> bool) -> Grid<bool> {
+    let size = grid.size();
+    let mut visible = Grid::new(size.width, size.height, false);
+    cast_shadows(grid, origin, radius, is_opaque, &mut |position| {
+        let _ = visible.set(position, true);
+    });
+    visible
+}
+
+/// all positions seen from `origin` within `radius`, see `get_field_of_view`
+pub fn get_visible_positions<G: GridRead + ?Sized>(
+    grid: &G,
+    origin: Position,
+    radius: usize,
+    is_opaque: &dyn Fn(&G::Tile) -> bool,
+) -> HashSet<Position> {
+    let mut visible = HashSet::new();
+    cast_shadows(grid, origin, radius, is_opaque, &mut |position| {
+        visible.insert(position);
+    });
+    visible
+}
+
+fn cast_shadows<G: GridRead + ?Sized>(
+    grid: &G,
+    origin: Position,
+    radius: usize,
+    is_opaque: &dyn Fn(&G::Tile) -> bool,
+    reveal: &mut dyn FnMut(Position),
+) {
+    if grid.get_ref(origin).is_none() {
+        return;
+    }
+    reveal(origin);
+
+    for quadrant in [Quadrant::North, Quadrant::East, Quadrant::South, Quadrant::West] {
+        let mut caster = ShadowCaster {
+            grid,
+            origin,
+            quadrant,
+            radius: radius as i64,
+            is_opaque,
+            reveal: &mut *reveal,
+        };
+        caster.scan(Row {
+            depth: 1,
+            start_slope: Slope::new(-1, 1),
+            end_slope: Slope::new(1, 1),
+        });
+    }
+}
+
+/// 90 degree section of the field of view, facing away from the origin
+#[derive(Clone, Copy)]
+enum Quadrant {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Quadrant {
+    /// grid position of the tile at `depth` rows away from `origin` and `column` tiles to the side
+    const fn transform(&self, origin: Position, depth: i64, column: i64) -> Position {
+        match self {
+            Quadrant::North => Position::new(origin.x + column, origin.y - depth),
+            Quadrant::South => Position::new(origin.x + column, origin.y + depth),
+            Quadrant::East => Position::new(origin.x + depth, origin.y + column),
+            Quadrant::West => Position::new(origin.x - depth, origin.y + column),
+        }
+    }
+}
+
+/// exact fraction to avoid floating point errors at the edges of shadows
+#[derive(Clone, Copy)]
+struct Slope {
+    numerator: i64,
+    denominator: i64,
+}
+
+impl Slope {
+    const fn new(numerator: i64, denominator: i64) -> Self {
+        Self { numerator, denominator }
+    }
+
+    /// slope to the left edge of the tile at `column` in a row at `depth`
+    const fn of_tile(depth: i64, column: i64) -> Self {
+        Self::new(2 * column - 1, 2 * depth)
+    }
+}
+
+struct Row {
+    depth: i64,
+    start_slope: Slope,
+    end_slope: Slope,
+}
+
+impl Row {
+    /// first and last column covered by the slopes, rounding to the closest tile
+    fn columns(&self) -> (i64, i64) {
+        let Slope { numerator, denominator } = self.start_slope;
+        let min_column = (2 * self.depth * numerator + denominator).div_euclid(2 * denominator);
+        let Slope { numerator, denominator } = self.end_slope;
+        let max_column = -(-(2 * self.depth * numerator - denominator)).div_euclid(2 * denominator);
+        (min_column, max_column)
+    }
+
+    /// whether the center of the tile at `column` is within the slopes, which makes the visibility symmetric
+    fn is_symmetric(&self, column: i64) -> bool {
+        column * self.start_slope.denominator >= self.depth * self.start_slope.numerator
+            && column * self.end_slope.denominator <= self.depth * self.end_slope.numerator
+    }
+
+    fn next(&self) -> Row {
+        Row {
+            depth: self.depth + 1,
+            start_slope: self.start_slope,
+            end_slope: self.end_slope,
+        }
+    }
+}
+
+struct ShadowCaster<'a, G: GridRead + ?Sized> {
+    grid: &'a G,
+    origin: Position,
+    quadrant: Quadrant,
+    radius: i64,
+    is_opaque: &'a dyn Fn(&G::Tile) -> bool,
+    reveal: &'a mut dyn FnMut(Position),
+}
+
+impl<G: GridRead + ?Sized> ShadowCaster<'_, G> {
+    fn scan(&mut self, mut row: Row) {
+        if row.depth > self.radius {
+            return;
+        }
+
+        let (min_column, max_column) = row.columns();
+        let mut previous_opaque = None;
+        for column in min_column..=max_column {
+            let position = self.quadrant.transform(self.origin, row.depth, column);
+            let tile = self.grid.get_ref(position);
+            let opaque = tile.is_none_or(self.is_opaque);
+
+            let in_radius = column * column + row.depth * row.depth <= self.radius * self.radius;
+            if in_radius && (opaque || row.is_symmetric(column)) && tile.is_some() {
+                (self.reveal)(position);
+            }
+
+            match (previous_opaque, opaque) {
+                (Some(true), false) => row.start_slope = Slope::of_tile(row.depth, column),
+                (Some(false), true) => {
+                    let mut next_row = row.next();
+                    next_row.end_slope = Slope::of_tile(row.depth, column);
+                    self.scan(next_row);
+                }
+                _ => {}
+            }
+            previous_opaque = Some(opaque);
+        }
+
+        if previous_opaque == Some(false) {
+            self.scan(row.next());
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use std::sync::Arc;
+
+use super::{
+    patterns::{adjacent_pattern, square_pattern, GridPattern, PatternPositions},
+    BoundedGrid, Grid, Position, RectSize,
+};
+
+/// which tiles count as connected to each other
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Connectivity {
+    /// only orthogonal neighbours are connected
+    #[default]
+    Four,
+    /// diagonal neighbours are connected as well
+    Eight,
+}
+
+impl Connectivity {
+    pub fn pattern(&self) -> Arc<GridPattern> {
+        match self {
+            Connectivity::Four => adjacent_pattern(),
+            Connectivity::Eight => square_pattern(1),
+        }
+    }
+}
+
+/// all positions connected to `start` whose tiles satisfy `is_part_of_region`, in the order they have been found.
+///
+/// the result is empty if `start` is outside of the grid or does not satisfy the predicate itself.
+/// regions of a wrapping grid continue across its seams.
+///
+/// # Examples
+/// ```
+/// // paint bucket tool
+/// let target = canvas[clicked];
+/// for position in flood_fill(&canvas, clicked, Connectivity::Four, &|&color| color == target) {
+///     canvas[position] = selected_color;
+/// }
+/// ```
+pub fn flood_fill<G: BoundedGrid + ?Sized>(
+    grid: &G,
+    start: Position,
+    connectivity: Connectivity,
+    is_part_of_region: &dyn Fn(&G::Tile) -> bool,
+) -> Vec<Position> {
+    let size = grid.size();
+    let Some(start) = grid.wrap_position(start).filter(|&start| grid.get_ref(start).is_some_and(is_part_of_region)) else {
+        return vec![];
+    };
+
+    let pattern = connectivity.pattern();
+    let mut visited = Grid::new(size.width, size.height, false);
+    visited[start] = true;
+    let mut open = vec![start];
+    let mut region = vec![];
+
+    while let Some(position) = open.pop() {
+        region.push(position);
+        for neighbor in pattern.iter_pattern_positions(position, false).filter_map(|neighbor| grid.wrap_position(neighbor)) {
+            if !visited[neighbor] && grid.get_ref(neighbor).is_some_and(is_part_of_region) {
+                visited[neighbor] = true;
+                open.push(neighbor);
+            }
+        }
+    }
+
+    region
+}
+
+/// all positions connected to `start` that hold a value equal to the one at `start`
+pub fn flood_fill_equal<G: BoundedGrid + ?Sized>(grid: &G, start: Position, connectivity: Connectivity) -> Vec<Position>
+where
+    G::Tile: PartialEq,
+{
+    let Some(value) = grid.get_ref(start) else {
+        return vec![];
+    };
+    flood_fill(grid, start, connectivity, &|tile| tile == value)
+}
+
+/// a connected region found by `get_connected_components`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Region {
+    pub label: usize,
+    /// amount of tiles in the region
+    pub size: usize,
+    /// first tile of the region in row major order, useful to look up the value the region consists of
+    pub first: Position,
+    /// top left corner of the bounding box, can be passed to `Grid::get_sub_grid` together with `bounds_size`
+    pub bounds_offset: Position,
+    pub bounds_size: RectSize,
+}
+
+/// label map of all connected regions of a grid, see `get_connected_components`
+#[derive(Debug, Clone)]
+pub struct ConnectedComponents {
+    /// label of the region every tile belongs to, labels are indices into `regions`
+    pub labels: Grid<usize>,
+    pub regions: Vec<Region>,
+}
+
+impl ConnectedComponents {
+    pub fn region_at(&self, position: Position) -> Option<&Region> {
+        self.regions.get(self.labels.get(position)?)
+    }
+
+    pub fn are_connected(&self, a: Position, b: Position) -> bool {
+        matches!((self.labels.get(a), self.labels.get(b)), (Some(label_a), Some(label_b)) if label_a == label_b)
+    }
+}
+
+/// splits the grid into regions of neighbouring tiles for which `are_connected` holds.
+///
+/// `are_connected` is expected to be an equivalence relation, e.g. comparing a property of both tiles.
+/// every tile belongs to exactly one region, regions are labeled in row major order of their first tile.
+/// the bounding box of a region crossing the seams of a wrapping grid spans the whole wrapped axis.
+///
+/// # Examples
+/// ```
+/// // is the dungeon fully connected?
+/// let components = get_connected_components(&dungeon, Connectivity::Four, &|a, b| a.is_floor() == b.is_floor());
+/// let floor_regions = components.regions.iter().filter(|region| dungeon[region.first].is_floor()).count();
+/// assert_eq!(floor_regions, 1);
+/// ```
+pub fn get_connected_components<G: BoundedGrid + ?Sized>(
+    grid: &G,
+    connectivity: Connectivity,
+    are_connected: &dyn Fn(&G::Tile, &G::Tile) -> bool,
+) -> ConnectedComponents {
+    const UNLABELED: usize = usize::MAX;
+
+    let pattern = connectivity.pattern();
+    let size = grid.size();
+    let mut labels = Grid::new(size.width, size.height, UNLABELED);
+    let mut regions = vec![];
+    let mut open = vec![];
+
+    for first in size.iter() {
+        let Some(value) = grid.get_ref(first).filter(|_| labels[first] == UNLABELED) else {
+            continue;
+        };
+
+        let label = regions.len();
+        let mut min = first;
+        let mut max = first;
+        let mut size = 0;
+        labels[first] = label;
+        open.push((first, value));
+
+        while let Some((position, value)) = open.pop() {
+            size += 1;
+            min = Position::new(min.x.min(position.x), min.y.min(position.y));
+            max = Position::new(max.x.max(position.x), max.y.max(position.y));
+
+            for neighbor in pattern.iter_pattern_positions(position, false).filter_map(|neighbor| grid.wrap_position(neighbor)) {
+                if labels[neighbor] != UNLABELED {
+                    continue;
+                }
+                if let Some(neighbor_value) = grid.get_ref(neighbor).filter(|neighbor_value| are_connected(value, neighbor_value)) {
+                    labels[neighbor] = label;
+                    open.push((neighbor, neighbor_value));
+                }
+            }
+        }
+
+        regions.push(Region {
+            label,
+            size,
+            first,
+            bounds_offset: min,
+            bounds_size: RectSize::new((max.x - min.x + 1) as usize, (max.y - min.y + 1) as usize),
+        });
+    }
+
+    ConnectedComponents { labels, regions }
+}
+
+/// regions of neighbouring tiles holding equal values
+pub fn get_equal_components<G: BoundedGrid + ?Sized>(grid: &G, connectivity: Connectivity) -> ConnectedComponents
+where
+    G::Tile: PartialEq,
+{
+    get_connected_components(grid, connectivity, &|a, b| a == b)
+}
@@ -0,0 +1,46 @@
+use std::{error::Error, fmt};
+
+use super::{Position, RectSize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GridError {
+    /// the position is not inside a grid of the given size
+    OutOfBounds { position: Position, size: RectSize },
+    /// the amount of values does not match the size of the grid
+    SizeMismatch { size: RectSize, value_count: usize },
+    /// a grid does not have the dimensions of the grid it is combined with
+    DimensionMismatch { expected: RectSize, actual: RectSize },
+    /// there is no layer at the index, see `LayeredGrid`
+    LayerNotFound { layer: usize, layer_count: usize },
+    /// an unbounded grid has no writable tile at the position
+    NotWritable { position: Position },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::OutOfBounds { position, size } => write!(
+                f,
+                "position ({}, {}) is out of bounds for a grid of size {}x{}",
+                position.x, position.y, size.width, size.height
+            ),
+            GridError::SizeMismatch { size, value_count } => write!(
+                f,
+                "a grid of size {}x{} requires {} values, got {}",
+                size.width,
+                size.height,
+                size.len(),
+                value_count
+            ),
+            GridError::DimensionMismatch { expected, actual } => write!(
+                f,
+                "expected a grid of size {}x{}, got {}x{}",
+                expected.width, expected.height, actual.width, actual.height
+            ),
+            GridError::LayerNotFound { layer, layer_count } => write!(f, "layer {layer} does not exist in a grid with {layer_count} layers"),
+            GridError::NotWritable { position } => write!(f, "position ({}, {}) can not be written to", position.x, position.y),
+        }
+    }
+}
+
+impl Error for GridError {}
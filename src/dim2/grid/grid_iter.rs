@@ -1,6 +1,10 @@
-use std::mem::{self, MaybeUninit};
+use std::{
+    iter::StepBy,
+    slice::{Iter, IterMut},
+    vec,
+};
 
-use super::{position::Position, rect_size::RectSize, to_grid_position};
+use super::{patterns::PatternPositions, position::Position, rect_size::RectSize, to_grid_position, Grid, GridView};
 
 pub struct GridIter<'a, T> {
     values: &'a [T],
@@ -52,14 +56,18 @@ impl<'a, T> Iterator for GridIterMut<'a, T> {
 }
 
 pub struct GridIntoIter<T> {
-    values: Box<[T]>,
+    values: vec::IntoIter<T>,
     size: RectSize,
     index: usize,
 }
 
 impl<T> GridIntoIter<T> {
     pub fn new(values: Box<[T]>, size: RectSize) -> Self {
-        GridIntoIter { values, size, index: 0 }
+        GridIntoIter {
+            values: values.into_vec().into_iter(),
+            size,
+            index: 0,
+        }
     }
 }
 
@@ -67,16 +75,104 @@ impl<T> Iterator for GridIntoIter<T> {
     type Item = (Position, T);
 
     fn next(&mut self) -> Option<(Position, T)> {
+        let value = self.values.next()?;
         let current_index = self.index;
         self.index += 1;
 
-        if current_index == self.values.len() {
-            return None;
-        }
+        Some((to_grid_position(current_index, self.size), value))
+    }
+}
+
+/// strided iterator over the values of a single grid column
+pub type Column<'a, T> = StepBy<Iter<'a, T>>;
+
+pub type ColumnMut<'a, T> = StepBy<IterMut<'a, T>>;
+
+impl<T> Grid<T> {
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        let width = self.size.width;
+        (y < self.size.height).then(|| &self.values[y * width..(y + 1) * width])
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> Option<&mut [T]> {
+        let width = self.size.width;
+        (y < self.size.height).then(|| &mut self.values[y * width..(y + 1) * width])
+    }
+
+    /// all rows from top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        let width = self.size.width;
+        (0..self.size.height).map(move |y| &self.values[y * width..(y + 1) * width])
+    }
+
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
+        let width = self.size.width;
+        let mut rest = &mut self.values[..];
+        (0..self.size.height).map(move |_| {
+            let (row, tail) = std::mem::take(&mut rest).split_at_mut(width);
+            rest = tail;
+            row
+        })
+    }
+
+    /// values of a column from top to bottom
+    pub fn column(&self, x: usize) -> Option<Column<'_, T>> {
+        (x < self.size.width).then(|| self.values[x..].iter().step_by(self.size.width))
+    }
+
+    pub fn column_mut(&mut self, x: usize) -> Option<ColumnMut<'_, T>> {
+        let width = self.size.width;
+        (x < width).then(|| self.values[x..].iter_mut().step_by(width))
+    }
+
+    /// all columns from left to right
+    pub fn columns(&self) -> impl Iterator<Item = Column<'_, T>> {
+        (0..self.size.width).map(move |x| self.values[x..].iter().step_by(self.size.width))
+    }
+
+    pub fn columns_mut(&mut self) -> impl Iterator<Item = impl Iterator<Item = &mut T>> {
+        let mut rest = Some(self.view_mut());
+        std::iter::from_fn(move || {
+            let view = rest.take().filter(|view| view.width() > 0)?;
+            let (column, tail) = view.split_at_column(1);
+            rest = Some(tail);
+            Some(column.into_iter_with_position().map(|(_, value)| value))
+        })
+    }
+
+    /// every window of `size` that fits into the grid together with its offset, in row major order
+    ///
+    /// # Examples
+    /// ```
+    /// // three in a row
+    /// let has_match = board.windows(RectSize::new(3, 1)).any(|(_, window)| {
+    ///     let first = window.get_ref(Position::ZERO);
+    ///     window.iter().all(|gem| Some(gem) == first)
+    /// });
+    /// ```
+    pub fn windows(&self, size: RectSize) -> impl Iterator<Item = (Position, GridView<'_, T>)> {
+        let view = self.view();
+        let fits = !size.is_empty() && size.width <= self.size.width && size.height <= self.size.height;
+        let (columns, rows) = if fits {
+            (self.size.width - size.width + 1, self.size.height - size.height + 1)
+        } else {
+            (0, 0)
+        };
+        (0..rows).flat_map(move |y| {
+            (0..columns).map(move |x| {
+                let offset = Position::from((x, y));
+                (offset, view.sub_view(offset, size).expect("the window fits into the grid"))
+            })
+        })
+    }
 
-        Some((
-            to_grid_position(current_index, self.size),
-            mem::replace(&mut self.values[current_index], unsafe { MaybeUninit::<T>::uninit().assume_init() }),
-        ))
+    /// positions of `pattern` around `center` that are inside of the grid together with their values, `center` itself is skipped.
+    ///
+    /// on a wrapping grid the positions are wrapped across the seams, so they always lie inside of the grid
+    pub fn neighbors<'a>(&'a self, center: Position, pattern: &'a dyn PatternPositions) -> impl Iterator<Item = (Position, &'a T)> {
+        pattern.iter_pattern_positions(center, false).filter_map(move |position| {
+            let position = self.wrap_position(position)?;
+            Some((position, self.get_ref(position)?))
+        })
     }
 }
@@ -0,0 +1,69 @@
+use super::{Grid, Position, RectSize, Topology};
+
+/// read access shared by all grid backends, lets the path finding and pattern functions run on any of them
+pub trait GridRead {
+    type Tile;
+
+    /// area covered by the grid starting at `(0, 0)`, `None` if the grid is unbounded
+    fn bounds(&self) -> Option<RectSize>;
+
+    fn get_ref(&self, position: Position) -> Option<&Self::Tile>;
+
+    fn topology(&self) -> Topology {
+        Topology::Bounded
+    }
+
+    fn get(&self, position: Position) -> Option<Self::Tile>
+    where
+        Self::Tile: Clone,
+    {
+        self.get_ref(position).cloned()
+    }
+
+    /// the position inside of the grid that `position` refers to, `None` if it is outside and the grid doesn't wrap
+    fn wrap_position(&self, position: Position) -> Option<Position> {
+        match self.bounds() {
+            Some(size) => self.topology().wrap(position, size),
+            None => Some(position),
+        }
+    }
+
+    fn contains(&self, position: Position) -> bool {
+        self.wrap_position(position).is_some()
+    }
+
+    /// shortest offset from `from` to `to`, crossing the seams of a wrapping grid where that is shorter
+    fn offset(&self, from: Position, to: Position) -> Position {
+        match self.bounds() {
+            Some(size) => self.topology().offset(from, to, size),
+            None => to - from,
+        }
+    }
+}
+
+impl<T> GridRead for Grid<T> {
+    type Tile = T;
+
+    fn bounds(&self) -> Option<RectSize> {
+        Some(self.size())
+    }
+
+    fn get_ref(&self, position: Position) -> Option<&T> {
+        Grid::get_ref(self, position)
+    }
+
+    fn topology(&self) -> Topology {
+        Grid::topology(self)
+    }
+}
+
+/// grid with a fixed size, required by the algorithms that produce a result for every tile
+pub trait BoundedGrid: GridRead {
+    fn size(&self) -> RectSize;
+}
+
+impl<T> BoundedGrid for Grid<T> {
+    fn size(&self) -> RectSize {
+        Grid::size(self)
+    }
+}
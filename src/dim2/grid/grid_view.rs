@@ -0,0 +1,376 @@
+use std::marker::PhantomData;
+
+use super::{BoundedGrid, Grid, GridError, GridRead, GridWrite, Position, RectSize};
+
+/// borrowed rectangular window of a grid with its own local coordinates, see `Grid::sub_view`
+#[derive(Debug)]
+pub struct GridView<'a, T> {
+    /// top left tile of the window. only the tiles inside of the window may be referenced,
+    /// the columns between its rows can be mutably borrowed by another view, see `GridViewMut::split_at_column`
+    origin: *const T,
+    /// width of the underlying grid
+    stride: usize,
+    size: RectSize,
+    _borrow: PhantomData<&'a T>,
+}
+
+// SAFETY: the view behaves like a `&` to the values inside its window
+unsafe impl<T: Sync> Send for GridView<'_, T> {}
+// SAFETY: the view only hands out shared references
+unsafe impl<T: Sync> Sync for GridView<'_, T> {}
+
+impl<T> Clone for GridView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GridView<'_, T> {}
+
+impl<'a, T> GridView<'a, T> {
+    pub const fn size(&self) -> RectSize {
+        self.size
+    }
+
+    pub const fn width(&self) -> usize {
+        self.size.width
+    }
+
+    pub const fn height(&self) -> usize {
+        self.size.height
+    }
+
+    pub const fn len(&self) -> usize {
+        self.size.width * self.size.height
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get_ref(&self, position: Position) -> Option<&'a T> {
+        // SAFETY: the position is inside the window, which is borrowed by this view
+        in_bounds(position, self.size).then(|| unsafe { &*self.origin.add(local_index(position, self.stride)) })
+    }
+
+    pub fn get(&self, position: Position) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.get_ref(position).cloned()
+    }
+
+    /// narrower view of `size` starting at `offset` in local coordinates
+    pub fn sub_view(&self, offset: Position, size: RectSize) -> Result<GridView<'a, T>, GridError> {
+        check_region(self.size, offset, size)?;
+        let origin = if size.is_empty() {
+            self.origin
+        } else {
+            // SAFETY: the offset is inside of the window
+            unsafe { self.origin.add(local_index(offset, self.stride)) }
+        };
+        Ok(GridView {
+            origin,
+            stride: self.stride,
+            size,
+            _borrow: PhantomData,
+        })
+    }
+
+    pub fn iter_with_position(&self) -> impl Iterator<Item = (Position, &'a T)> + 'a {
+        let (origin, stride, size) = (self.origin, self.stride, self.size);
+        let height = if size.is_empty() { 0 } else { size.height };
+        (0..height).flat_map(move |y| {
+            // SAFETY: each row slice only covers the tiles of the window in that row
+            let row: &'a [T] = unsafe { std::slice::from_raw_parts(origin.add(y * stride), size.width) };
+            row.iter().enumerate().map(move |(x, value)| (Position::from((x, y)), value))
+        })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + 'a {
+        self.iter_with_position().map(|(_, value)| value)
+    }
+
+    /// copies the viewed values into a new grid
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::with_preset_values(self.width(), self.height(), self.iter().cloned().collect())
+    }
+}
+
+/// mutably borrowed rectangular window of a grid with its own local coordinates, see `Grid::sub_view_mut`.
+///
+/// a view can be split into disjoint views that can be edited at the same time.
+#[derive(Debug)]
+pub struct GridViewMut<'a, T> {
+    /// top left tile of the window
+    origin: *mut T,
+    /// width of the underlying grid
+    stride: usize,
+    size: RectSize,
+    _borrow: PhantomData<&'a mut T>,
+}
+
+// SAFETY: the view behaves like a `&mut` to the values inside its window
+unsafe impl<T: Send> Send for GridViewMut<'_, T> {}
+// SAFETY: shared access to the view only hands out shared references
+unsafe impl<T: Sync> Sync for GridViewMut<'_, T> {}
+
+impl<'a, T> GridViewMut<'a, T> {
+    fn new(values: &'a mut [T], stride: usize, offset: Position, size: RectSize) -> Self {
+        let window = window_mut(values, stride, offset, size);
+        Self {
+            origin: window.as_mut_ptr(),
+            stride,
+            size,
+            _borrow: PhantomData,
+        }
+    }
+
+    pub const fn size(&self) -> RectSize {
+        self.size
+    }
+
+    pub const fn width(&self) -> usize {
+        self.size.width
+    }
+
+    pub const fn height(&self) -> usize {
+        self.size.height
+    }
+
+    pub const fn len(&self) -> usize {
+        self.size.width * self.size.height
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get_ref(&self, position: Position) -> Option<&T> {
+        // SAFETY: the position is inside the window, which is borrowed by this view
+        in_bounds(position, self.size).then(|| unsafe { &*self.origin.add(local_index(position, self.stride)) })
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        // SAFETY: the position is inside the window, which is exclusively borrowed by this view
+        in_bounds(position, self.size).then(|| unsafe { &mut *self.origin.add(local_index(position, self.stride)) })
+    }
+
+    pub fn get(&self, position: Position) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.get_ref(position).cloned()
+    }
+
+    pub fn set(&mut self, position: Position, value: T) -> Result<(), GridError> {
+        let size = self.size;
+        *self.get_mut(position).ok_or(GridError::OutOfBounds { position, size })? = value;
+        Ok(())
+    }
+
+    /// read only view of the same window
+    pub fn as_view(&self) -> GridView<'_, T> {
+        GridView {
+            origin: self.origin,
+            stride: self.stride,
+            size: self.size,
+            _borrow: PhantomData,
+        }
+    }
+
+    pub fn sub_view(&self, offset: Position, size: RectSize) -> Result<GridView<'_, T>, GridError> {
+        self.as_view().sub_view(offset, size)
+    }
+
+    /// narrower mutable view of `size` starting at `offset` in local coordinates
+    pub fn sub_view_mut(&mut self, offset: Position, size: RectSize) -> Result<GridViewMut<'_, T>, GridError> {
+        check_region(self.size, offset, size)?;
+        Ok(self.reborrow_region(offset, size))
+    }
+
+    /// splits the view into the columns left of `x` and the remaining ones
+    pub fn split_at_column(self, x: usize) -> (GridViewMut<'a, T>, GridViewMut<'a, T>) {
+        let x = x.min(self.size.width);
+        let left = self.reborrow_region(Position::ZERO, RectSize::new(x, self.size.height));
+        let right = self.reborrow_region(Position::from((x, 0)), RectSize::new(self.size.width - x, self.size.height));
+        (left, right)
+    }
+
+    /// splits the view into the rows above `y` and the remaining ones
+    pub fn split_at_row(self, y: usize) -> (GridViewMut<'a, T>, GridViewMut<'a, T>) {
+        let y = y.min(self.size.height);
+        let top = self.reborrow_region(Position::ZERO, RectSize::new(self.size.width, y));
+        let bottom = self.reborrow_region(Position::from((0, y)), RectSize::new(self.size.width, self.size.height - y));
+        (top, bottom)
+    }
+
+    pub fn iter_with_position(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.as_view().iter_with_position()
+    }
+
+    pub fn iter_mut_with_position(&mut self) -> impl Iterator<Item = (Position, &mut T)> {
+        self.reborrow_region(Position::ZERO, self.size).into_iter_with_position()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.iter_mut_with_position().map(|(_, value)| value)
+    }
+
+    /// iterates the window for as long as the view borrows it
+    pub fn into_iter_with_position(self) -> impl Iterator<Item = (Position, &'a mut T)> {
+        let (origin, stride, size) = (self.origin, self.stride, self.size);
+        (0..size.height).flat_map(move |y| {
+            (0..size.width).map(move |x| {
+                let position = Position::from((x, y));
+                // SAFETY: every position inside the window is visited exactly once, so the references don't alias
+                (position, unsafe { &mut *origin.add(local_index(position, stride)) })
+            })
+        })
+    }
+
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.as_view().to_grid()
+    }
+
+    /// view of a region that has to be inside of this view, borrowing it for any lifetime the caller picks
+    fn reborrow_region<'b>(&self, offset: Position, size: RectSize) -> GridViewMut<'b, T> {
+        let origin = if size.is_empty() {
+            self.origin
+        } else {
+            // SAFETY: the offset is inside of the window
+            unsafe { self.origin.add(local_index(offset, self.stride)) }
+        };
+        GridViewMut {
+            origin,
+            stride: self.stride,
+            size,
+            _borrow: PhantomData,
+        }
+    }
+}
+
+impl<T> GridRead for GridView<'_, T> {
+    type Tile = T;
+
+    fn bounds(&self) -> Option<RectSize> {
+        Some(self.size)
+    }
+
+    fn get_ref(&self, position: Position) -> Option<&T> {
+        GridView::get_ref(self, position)
+    }
+}
+
+impl<T> BoundedGrid for GridView<'_, T> {
+    fn size(&self) -> RectSize {
+        self.size
+    }
+}
+
+impl<T> GridRead for GridViewMut<'_, T> {
+    type Tile = T;
+
+    fn bounds(&self) -> Option<RectSize> {
+        Some(self.size)
+    }
+
+    fn get_ref(&self, position: Position) -> Option<&T> {
+        GridViewMut::get_ref(self, position)
+    }
+}
+
+impl<T> BoundedGrid for GridViewMut<'_, T> {
+    fn size(&self) -> RectSize {
+        self.size
+    }
+}
+
+impl<T> GridWrite for GridViewMut<'_, T> {
+    fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        GridViewMut::get_mut(self, position)
+    }
+}
+
+impl<T> Grid<T> {
+    /// read only view of the whole grid
+    pub fn view(&self) -> GridView<'_, T> {
+        GridView {
+            origin: self.values.as_ptr(),
+            stride: self.size.width,
+            size: self.size,
+            _borrow: PhantomData,
+        }
+    }
+
+    /// mutable view of the whole grid
+    pub fn view_mut(&mut self) -> GridViewMut<'_, T> {
+        let (stride, size) = (self.size.width, self.size);
+        GridViewMut::new(&mut self.values, stride, Position::ZERO, size)
+    }
+
+    /// borrows the region of `size` starting at `offset` without cloning, the region has to be completely inside of the grid
+    pub fn sub_view(&self, offset: Position, size: RectSize) -> Result<GridView<'_, T>, GridError> {
+        self.view().sub_view(offset, size)
+    }
+
+    /// mutably borrows the region of `size` starting at `offset`, the region has to be completely inside of the grid
+    pub fn sub_view_mut(&mut self, offset: Position, size: RectSize) -> Result<GridViewMut<'_, T>, GridError> {
+        check_region(self.size, offset, size)?;
+        let stride = self.size.width;
+        Ok(GridViewMut::new(&mut self.values, stride, offset, size))
+    }
+}
+
+/// makes sure the region of `size` at `offset` fits into `bounds`,
+/// reporting the first position outside in row major order otherwise
+pub(crate) fn check_region(bounds: RectSize, offset: Position, size: RectSize) -> Result<(), GridError> {
+    let out_of_bounds = |position| Err(GridError::OutOfBounds { position, size: bounds });
+    if offset.x < 0 || offset.y < 0 || offset.x as usize > bounds.width || offset.y as usize > bounds.height {
+        return out_of_bounds(offset);
+    }
+    if size.is_empty() {
+        return Ok(());
+    }
+    if !in_bounds(offset, bounds) {
+        return out_of_bounds(offset);
+    }
+    if offset.x as usize + size.width > bounds.width {
+        return out_of_bounds(Position::new(bounds.width as i64, offset.y));
+    }
+    if offset.y as usize + size.height > bounds.height {
+        return out_of_bounds(Position::new(offset.x, bounds.height as i64));
+    }
+    Ok(())
+}
+
+const fn in_bounds(position: Position, size: RectSize) -> bool {
+    position.x >= 0 && position.y >= 0 && (position.x as usize) < size.width && (position.y as usize) < size.height
+}
+
+const fn local_index(position: Position, stride: usize) -> usize {
+    position.y as usize * stride + position.x as usize
+}
+
+/// amount of values between the top left and the bottom right tile of a window, both included
+const fn window_length(stride: usize, size: RectSize) -> usize {
+    if size.width == 0 || size.height == 0 {
+        0
+    } else {
+        (size.height - 1) * stride + size.width
+    }
+}
+
+fn window_mut<T>(values: &mut [T], stride: usize, offset: Position, size: RectSize) -> &mut [T] {
+    if size.is_empty() {
+        return &mut values[..0];
+    }
+    let start = local_index(offset, stride);
+    &mut values[start..start + window_length(stride, size)]
+}
@@ -0,0 +1,26 @@
+use super::{Grid, GridError, GridRead, Position};
+
+/// write access shared by all grid backends
+pub trait GridWrite: GridRead {
+    fn get_mut(&mut self, position: Position) -> Option<&mut Self::Tile>;
+
+    fn set(&mut self, position: Position, value: Self::Tile) -> Result<(), GridError> {
+        let error = match self.bounds() {
+            Some(size) => GridError::OutOfBounds { position, size },
+            None => GridError::NotWritable { position },
+        };
+        let tile = self.get_mut(position).ok_or(error)?;
+        *tile = value;
+        Ok(())
+    }
+}
+
+impl<T> GridWrite for Grid<T> {
+    fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        Grid::get_mut(self, position)
+    }
+
+    fn set(&mut self, position: Position, value: T) -> Result<(), GridError> {
+        Grid::set(self, position, value)
+    }
+}
@@ -0,0 +1,190 @@
+use super::{BitGrid, Grid, GridError, GridViewMut, Position, RectSize};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Layer<T> {
+    name: String,
+    grid: Grid<Option<T>>,
+}
+
+/// stack of named layers of the same size, e.g. terrain, objects, decorations and collision.
+///
+/// layers are indexed from the bottom, index `0` is the lowest layer. empty tiles are `None`.
+/// layer names don't have to be unique, lookups by name use the lowest layer with that name.
+///
+/// # Examples
+/// ```
+/// let mut map = LayeredGrid::new(RectSize::new(64, 64));
+/// let terrain = map.add_layer("terrain");
+/// let objects = map.add_layer("objects");
+/// map.set(objects, chest_position, Some(Tile::Chest)).unwrap();
+///
+/// let (_, tile) = map.topmost(cursor).unwrap();
+/// let walkable = map.walkability(&|tiles| tiles[terrain] != Some(&Tile::Water) && tiles[objects].is_none());
+/// let path = get_shortest_path(&walkable, start, end, &|&walkable| walkable);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayeredGrid<T> {
+    size: RectSize,
+    layers: Vec<Layer<T>>,
+}
+
+impl<T> LayeredGrid<T> {
+    /// map without any layers
+    pub const fn new(size: RectSize) -> Self {
+        Self { size, layers: vec![] }
+    }
+
+    pub const fn size(&self) -> RectSize {
+        self.size
+    }
+
+    pub fn layer_count(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// adds an empty layer on top, returns its index
+    pub fn add_layer(&mut self, name: impl Into<String>) -> usize {
+        let grid = Grid::with_default_values(self.size.width, self.size.height);
+        self.layers.push(Layer { name: name.into(), grid });
+        self.layers.len() - 1
+    }
+
+    /// adds `grid` as layer on top, returns its index
+    pub fn add_layer_grid(&mut self, name: impl Into<String>, grid: Grid<Option<T>>) -> Result<usize, GridError> {
+        self.insert_layer_grid(self.layers.len(), name, grid)?;
+        Ok(self.layers.len() - 1)
+    }
+
+    /// inserts `grid` as layer at `index`, moving the layers from `index` upwards
+    ///
+    /// # Panics
+    /// if `index` is greater than the amount of layers
+    pub fn insert_layer_grid(&mut self, index: usize, name: impl Into<String>, grid: Grid<Option<T>>) -> Result<(), GridError> {
+        if grid.size() != self.size {
+            return Err(GridError::DimensionMismatch {
+                expected: self.size,
+                actual: grid.size(),
+            });
+        }
+
+        self.layers.insert(index, Layer { name: name.into(), grid });
+        Ok(())
+    }
+
+    /// removes the layer at `index` and returns its name and tiles, the layers above move down
+    ///
+    /// # Panics
+    /// if there is no layer at `index`
+    pub fn remove_layer(&mut self, index: usize) -> (String, Grid<Option<T>>) {
+        let layer = self.layers.remove(index);
+        (layer.name, layer.grid)
+    }
+
+    /// moves the layer at `from` to `to`, shifting the layers in between
+    ///
+    /// # Panics
+    /// if there is no layer at `from` or `to`
+    pub fn move_layer(&mut self, from: usize, to: usize) {
+        let layer = self.layers.remove(from);
+        self.layers.insert(to, layer);
+    }
+
+    /// # Panics
+    /// if there is no layer at `a` or `b`
+    pub fn swap_layers(&mut self, a: usize, b: usize) {
+        self.layers.swap(a, b);
+    }
+
+    /// index of the lowest layer called `name`
+    pub fn layer_index(&self, name: &str) -> Option<usize> {
+        self.layers.iter().position(|layer| layer.name == name)
+    }
+
+    pub fn layer_name(&self, index: usize) -> Option<&str> {
+        self.layers.get(index).map(|layer| layer.name.as_str())
+    }
+
+    /// # Panics
+    /// if there is no layer at `index`
+    pub fn rename_layer(&mut self, index: usize, name: impl Into<String>) {
+        self.layers[index].name = name.into();
+    }
+
+    pub fn layer(&self, index: usize) -> Option<&Grid<Option<T>>> {
+        self.layers.get(index).map(|layer| &layer.grid)
+    }
+
+    /// tiles of the layer can be changed, but not its size, so all layers stay aligned
+    pub fn layer_mut(&mut self, index: usize) -> Option<GridViewMut<'_, Option<T>>> {
+        self.layers.get_mut(index).map(|layer| layer.grid.view_mut())
+    }
+
+    pub fn layer_by_name(&self, name: &str) -> Option<&Grid<Option<T>>> {
+        self.layer(self.layer_index(name)?)
+    }
+
+    pub fn layer_by_name_mut(&mut self, name: &str) -> Option<GridViewMut<'_, Option<T>>> {
+        self.layer_mut(self.layer_index(name)?)
+    }
+
+    /// all layers with their name from the bottom to the top
+    pub fn layers(&self) -> impl Iterator<Item = (&str, &Grid<Option<T>>)> {
+        self.layers.iter().map(|layer| (layer.name.as_str(), &layer.grid))
+    }
+
+    /// tile of the layer at `layer`, `None` if it is empty, outside of the grid or the layer doesn't exist
+    pub fn get_ref(&self, layer: usize, position: Position) -> Option<&T> {
+        self.layer(layer)?.get_ref(position)?.as_ref()
+    }
+
+    pub fn set(&mut self, layer: usize, position: Position, value: Option<T>) -> Result<(), GridError> {
+        let layer_count = self.layers.len();
+        let layer = self.layers.get_mut(layer).ok_or(GridError::LayerNotFound { layer, layer_count })?;
+        layer.grid.set(position, value)
+    }
+
+    /// highest non-empty tile at `position` together with its layer index
+    pub fn topmost(&self, position: Position) -> Option<(usize, &T)> {
+        self.tiles_at(position).last()
+    }
+
+    /// all non-empty tiles at `position` with their layer index, from the bottom to the top
+    pub fn tiles_at(&self, position: Position) -> impl Iterator<Item = (usize, &T)> {
+        self.layers
+            .iter()
+            .enumerate()
+            .filter_map(move |(index, layer)| Some((index, layer.grid.get_ref(position)?.as_ref()?)))
+    }
+
+    /// grid of values computed from the tiles of all layers at every position.
+    ///
+    /// `combine` receives one entry per layer, indexed like the layers.
+    ///
+    /// # Examples
+    /// ```
+    /// let tile_costs = map.combine_layers(&|tiles| match tiles[terrain] {
+    ///     Some(Tile::Road) => Some(1),
+    ///     Some(Tile::Water) | None => None,
+    ///     _ => Some(3),
+    /// });
+    /// let path = get_shortest_weighted_path(&tile_costs, start, end, Movement::Orthogonal, &|&cost| cost);
+    /// ```
+    pub fn combine_layers<U>(&self, combine: &dyn Fn(&[Option<&T>]) -> U) -> Grid<U> {
+        let mut tiles = Vec::with_capacity(self.layers.len());
+        let values: Vec<_> = self
+            .size
+            .iter()
+            .map(|position| {
+                tiles.clear();
+                tiles.extend(self.layers.iter().map(|layer| layer.grid[position].as_ref()));
+                combine(&tiles)
+            })
+            .collect();
+        Grid::with_preset_values(self.size.width, self.size.height, values.into_boxed_slice())
+    }
+
+    /// mask of all positions for which `is_walkable` holds for the tiles of all layers, see `combine_layers`
+    pub fn walkability(&self, is_walkable: &dyn Fn(&[Option<&T>]) -> bool) -> BitGrid {
+        BitGrid::from(&self.combine_layers(is_walkable))
+    }
+}
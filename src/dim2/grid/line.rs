@@ -0,0 +1,145 @@
+use super::{GridRead, GridWrite, Position};
+
+/// positions on the straight line between two grid positions, including both ends.
+///
+/// uses Bresenham's algorithm, so consecutive positions may be diagonal neighbours.
+#[derive(Debug, Clone)]
+pub struct BresenhamLine {
+    current: Position,
+    end: Position,
+    delta: Position,
+    step: Position,
+    error: i64,
+    finished: bool,
+}
+
+impl BresenhamLine {
+    pub fn new(from: Position, to: Position) -> Self {
+        let delta = Position::new((to.x - from.x).abs(), -(to.y - from.y).abs());
+        Self {
+            current: from,
+            end: to,
+            delta,
+            step: Position::new((to.x - from.x).signum(), (to.y - from.y).signum()),
+            error: delta.x + delta.y,
+            finished: false,
+        }
+    }
+}
+
+impl Iterator for BresenhamLine {
+    type Item = Position;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let position = self.current;
+        if position == self.end {
+            self.finished = true;
+            return Some(position);
+        }
+
+        let doubled_error = 2 * self.error;
+        if doubled_error >= self.delta.y {
+            self.error += self.delta.y;
+            self.current.x += self.step.x;
+        }
+        if doubled_error <= self.delta.x {
+            self.error += self.delta.x;
+            self.current.y += self.step.y;
+        }
+
+        Some(position)
+    }
+}
+
+/// every position the straight line between the centers of two grid positions touches, including both ends.
+///
+/// unlike `BresenhamLine` the line never skips past a corner, which makes it suitable for strict
+/// collision checks. when the line passes exactly through a corner, both tiles next to it are part of the line.
+#[derive(Debug, Clone)]
+pub struct SupercoverLine {
+    current: Position,
+    distance: Position,
+    step: Position,
+    /// steps already taken horizontally and vertically
+    progress: Position,
+    /// tiles next to a crossed corner, returned before moving on
+    corner_tiles: Vec<Position>,
+    finished: bool,
+}
+
+impl SupercoverLine {
+    pub fn new(from: Position, to: Position) -> Self {
+        Self {
+            current: from,
+            distance: Position::new((to.x - from.x).abs(), (to.y - from.y).abs()),
+            step: Position::new((to.x - from.x).signum(), (to.y - from.y).signum()),
+            progress: Position::ZERO,
+            corner_tiles: Vec::with_capacity(2),
+            finished: false,
+        }
+    }
+}
+
+impl Iterator for SupercoverLine {
+    type Item = Position;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(corner_tile) = self.corner_tiles.pop() {
+            return Some(corner_tile);
+        }
+        if self.finished {
+            return None;
+        }
+
+        let position = self.current;
+        if self.progress.x >= self.distance.x && self.progress.y >= self.distance.y {
+            self.finished = true;
+            return Some(position);
+        }
+
+        // compares the distances to the next vertical and horizontal tile border
+        let decision = (1 + 2 * self.progress.x) * self.distance.y - (1 + 2 * self.progress.y) * self.distance.x;
+        if decision == 0 {
+            self.corner_tiles.push(Position::new(position.x, position.y + self.step.y));
+            self.corner_tiles.push(Position::new(position.x + self.step.x, position.y));
+            self.current += self.step;
+            self.progress += Position::new(1, 1);
+        } else if decision < 0 {
+            self.current.x += self.step.x;
+            self.progress.x += 1;
+        } else {
+            self.current.y += self.step.y;
+            self.progress.y += 1;
+        }
+
+        Some(position)
+    }
+}
+
+/// whether nothing blocks the sight between `from` and `to` along their Bresenham line.
+///
+/// only the tiles in between are checked, so e.g. a wall can be seen itself.
+/// returns `false` if either position is outside of the grid.
+pub fn has_line_of_sight<G: GridRead + ?Sized>(grid: &G, from: Position, to: Position, is_blocking: &dyn Fn(&G::Tile) -> bool) -> bool {
+    if grid.get_ref(from).is_none() || grid.get_ref(to).is_none() {
+        return false;
+    }
+
+    BresenhamLine::new(from, to)
+        .filter(|&position| position != from && position != to)
+        .all(|position| grid.get_ref(position).is_some_and(|tile| !is_blocking(tile)))
+}
+
+/// sets every tile on the Bresenham line between `from` and `to` to `value`, positions outside of the grid are skipped
+pub fn draw_line<G: GridWrite + ?Sized>(grid: &mut G, from: Position, to: Position, value: G::Tile)
+where
+    G::Tile: Clone,
+{
+    for position in BresenhamLine::new(from, to) {
+        let _ = grid.set(position, value.clone());
+    }
+}
@@ -3,15 +3,43 @@ use std::slice::{Iter, IterMut};
 
 use grid_iter::{GridIntoIter, GridIter, GridIterMut};
 
+pub mod bit_grid;
+pub use bit_grid::BitGrid;
+pub mod chunked_grid;
+pub use chunked_grid::{ChunkFactory, ChunkedGrid, UnloadCallback};
+pub mod direction;
+pub use direction::Direction;
+pub mod field_of_view;
+pub mod flood_fill;
+pub mod grid_error;
+pub use grid_error::GridError;
 pub mod grid_iter;
+pub mod grid_read;
+pub use grid_read::{BoundedGrid, GridRead};
+pub mod grid_write;
+pub use grid_write::GridWrite;
+pub mod grid_view;
+pub use grid_view::{GridView, GridViewMut};
+pub mod layered_grid;
+pub use layered_grid::LayeredGrid;
+pub mod line;
+mod paste;
 pub mod pathing;
 pub mod patterns;
 pub mod position;
 pub use position::Position;
 pub mod rect_size;
 pub use rect_size::RectSize;
+pub mod resize;
+pub use resize::Anchor;
 pub mod screen_translation;
 pub use screen_translation::*;
+pub mod sparse_grid;
+pub use sparse_grid::SparseGrid;
+pub mod topology;
+pub use topology::Topology;
+pub mod transform;
+pub use transform::Rotation;
 
 pub fn to_grid_index(position: Position, size: RectSize) -> usize {
     position.y as usize * size.width + position.x as usize
@@ -24,10 +52,11 @@ pub fn to_grid_position(index: usize, size: RectSize) -> Position {
     }
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Grid<T> {
     size: RectSize,
     values: Box<[T]>,
+    topology: Topology,
 }
 
 impl<T> Grid<T> {
@@ -47,14 +76,33 @@ impl<T> Grid<T> {
         Self::with_preset_values(width, height, values.into_boxed_slice())
     }
 
+    /// # Panics
+    /// if the amount of values does not match the size, see `try_with_preset_values` for a fallible version
     pub const fn with_preset_values(width: usize, height: usize, values: Box<[T]>) -> Self {
-        assert!(values.len() == height * width);
+        assert!(values.len() == height * width, "amount of values does not match the grid size");
         Self {
             size: RectSize { width, height },
             values,
+            topology: Topology::Bounded,
         }
     }
 
+    pub fn try_with_preset_values(width: usize, height: usize, values: Box<[T]>) -> Result<Self, GridError> {
+        let size = RectSize { width, height };
+        if values.len() != size.len() {
+            return Err(GridError::SizeMismatch {
+                size,
+                value_count: values.len(),
+            });
+        }
+
+        Ok(Self {
+            size,
+            values,
+            topology: Topology::Bounded,
+        })
+    }
+
     pub const fn size(&self) -> RectSize {
         self.size
     }
@@ -75,67 +123,71 @@ impl<T> Grid<T> {
         self.len() == 0
     }
 
+    pub const fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    /// how positions beyond the edges are treated by `get`, `set` and indexing, grids are bounded by default
+    pub fn set_topology(&mut self, topology: Topology) {
+        self.topology = topology;
+    }
+
+    /// # Examples
+    /// ```
+    /// let mut board = Grid::new(20, 20, Cell::Empty).with_topology(Topology::Torus);
+    /// board.set(Position::new(-1, 0), Cell::Snake).unwrap(); // sets (19, 0)
+    /// ```
+    pub fn with_topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    /// the position inside of the grid that `position` refers to, `None` if it is outside and the grid doesn't wrap
+    pub fn wrap_position(&self, position: Position) -> Option<Position> {
+        self.topology.wrap(position, self.size)
+    }
+
     pub fn get(&self, position: Position) -> Option<T>
     where
         T: Clone,
     {
-        if self.in_bounds(position) {
-            Some(self.values[to_grid_index(position, self.size)].clone())
-        } else {
-            None
-        }
+        self.get_ref(position).cloned()
     }
 
-    // TODO: add error type
-    pub fn set(&mut self, position: Position, value: T) -> Result<(), ()> {
-        if self.in_bounds(position) {
-            self.values[to_grid_index(position, self.size)] = value;
-            Ok(())
-        } else {
-            Err(())
-        }
+    pub fn set(&mut self, position: Position, value: T) -> Result<(), GridError> {
+        let index = self.checked_index(position)?;
+        self.values[index] = value;
+        Ok(())
     }
 
     pub fn get_ref(&self, position: Position) -> Option<&T> {
-        if self.in_bounds(position) {
-            Some(&self.values[to_grid_index(position, self.size)])
-        } else {
-            None
-        }
+        let index = self.checked_index(position).ok()?;
+        Some(&self.values[index])
     }
 
     pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
-        if self.in_bounds(position) {
-            Some(&mut self.values[to_grid_index(position, self.size)])
-        } else {
-            None
-        }
+        let index = self.checked_index(position).ok()?;
+        Some(&mut self.values[index])
     }
 
-    pub fn get_sub_grid(&self, offset: Position, size: RectSize) -> Option<Grid<T>>
+    /// copies the region of `size` starting at `offset`, which has to be completely inside of the grid
+    pub fn get_sub_grid(&self, offset: Position, size: RectSize) -> Result<Grid<T>, GridError>
     where
         T: Clone,
     {
-        let mut values = Vec::new();
+        let mut values = Vec::with_capacity(size.len());
         for pos in size.iter() {
-            let grid_pos = offset + pos;
-            if !self.in_bounds(offset + pos) {
-                return None;
-            }
-
-            values.push(self.values[to_grid_index(grid_pos, self.size)].clone());
+            values.push(self.values[self.checked_index(offset + pos)?].clone());
         }
 
-        Some(Grid::with_preset_values(size.width, size.height, values.into_boxed_slice()))
+        Ok(Grid::with_preset_values(size.width, size.height, values.into_boxed_slice()))
     }
 
-    pub fn swap(&mut self, position1: Position, position2: Position) -> Result<(), ()>
-    where
-        T: Clone,
-    {
-        let val1 = self.get(position1).ok_or(())?.clone();
-        self.set(position1, self.get(position2).ok_or(())?)?;
-        self.set(position2, val1)
+    pub fn swap(&mut self, position1: Position, position2: Position) -> Result<(), GridError> {
+        let index1 = self.checked_index(position1)?;
+        let index2 = self.checked_index(position2)?;
+        self.values.swap(index1, index2);
+        Ok(())
     }
 
     pub fn into_array(self) -> Box<[T]> {
@@ -175,50 +227,45 @@ impl<T> Grid<T> {
     where
         F: FnMut(&T) -> B,
     {
-        Grid::with_preset_values(self.width(), self.height(), self.values.iter().map(f).collect())
+        Grid::with_preset_values(self.width(), self.height(), self.values.iter().map(f).collect()).with_topology(self.topology)
+    }
+
+    fn checked_index(&self, position: Position) -> Result<usize, GridError> {
+        match self.wrap_position(position) {
+            Some(position) => Ok(to_grid_index(position, self.size)),
+            None => Err(GridError::OutOfBounds { position, size: self.size }),
+        }
     }
 
-    const fn in_bounds(&self, position: Position) -> bool {
-        position.x >= 0 && position.y >= 0 && (position.x as usize) < self.size.width && (position.y as usize) < self.size.height
+    fn index_of(&self, position: Position) -> usize {
+        self.checked_index(position).unwrap_or_else(|error| panic!("{error}"))
     }
 }
 
-impl<T> Index<(usize, usize)> for &Grid<T>
-where
-    T: Copy,
-{
+impl<T> Index<(usize, usize)> for &Grid<T> {
     type Output = T;
     fn index(&self, index: (usize, usize)) -> &Self::Output {
-        &self.values[to_grid_index(Position::from(index), self.size)]
+        &self.values[self.index_of(Position::from(index))]
     }
 }
 
-impl<T> Index<Position> for &Grid<T>
-where
-    T: Copy,
-{
+impl<T> Index<Position> for &Grid<T> {
     type Output = T;
     fn index(&self, index: Position) -> &Self::Output {
-        &self.values[to_grid_index(index, self.size)]
+        &self.values[self.index_of(index)]
     }
 }
 
-impl<T> Index<Position> for Grid<T>
-where
-    T: Copy,
-{
+impl<T> Index<Position> for Grid<T> {
     type Output = T;
     fn index(&self, index: Position) -> &Self::Output {
-        &self.values[to_grid_index(index, self.size)]
+        &self.values[self.index_of(index)]
     }
 }
 
-impl<T> IndexMut<Position> for Grid<T>
-where
-    T: Copy,
-{
+impl<T> IndexMut<Position> for Grid<T> {
     fn index_mut(&mut self, index: Position) -> &mut Self::Output {
-        &mut self.values[to_grid_index(index, self.size)]
+        &mut self.values[self.index_of(index)]
     }
 }
 
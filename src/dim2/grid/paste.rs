@@ -0,0 +1,55 @@
+use super::{Grid, Position, Topology};
+
+impl<T> Grid<T> {
+    /// copies all values of `source` into this grid with the top left corner of `source` at `offset`.
+    ///
+    /// values that would end up outside of this grid are clipped, even if the grid wraps.
+    /// returns the positions that have been written, in row major order of `source`.
+    pub fn paste(&mut self, source: &Grid<T>, offset: Position) -> Vec<Position>
+    where
+        T: Clone,
+    {
+        self.paste_with(source, offset, &mut |target, value| {
+            *target = value.clone();
+            true
+        })
+    }
+
+    /// like `paste`, but skips all values of `source` that are transparent
+    ///
+    /// # Examples
+    /// ```
+    /// // stamp a prefab room into the dungeon, keeping the existing tiles wherever the prefab is empty
+    /// let written = dungeon.paste_transparent(&room, room_offset, &|tile| *tile == Tile::Empty);
+    /// ```
+    pub fn paste_transparent(&mut self, source: &Grid<T>, offset: Position, is_transparent: &dyn Fn(&T) -> bool) -> Vec<Position>
+    where
+        T: Clone,
+    {
+        self.paste_with(source, offset, &mut |target, value| {
+            if is_transparent(value) {
+                return false;
+            }
+            *target = value.clone();
+            true
+        })
+    }
+
+    /// merges every value of `source` into the value it overlaps, with the top left corner of `source` at `offset`.
+    ///
+    /// `merge` returns whether it has written to the target value. values outside of this grid are clipped like in `paste`.
+    /// returns the positions that have been written, in row major order of `source`.
+    pub fn paste_with<U>(&mut self, source: &Grid<U>, offset: Position, merge: &mut dyn FnMut(&mut T, &U) -> bool) -> Vec<Position> {
+        let mut written = vec![];
+        for (source_position, value) in source.iter_with_position() {
+            let position = source_position + offset;
+            if Topology::Bounded.wrap(position, self.size).is_none() {
+                continue;
+            }
+            if merge(&mut self[position], value) {
+                written.push(position);
+            }
+        }
+        written
+    }
+}
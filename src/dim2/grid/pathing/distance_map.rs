@@ -0,0 +1,82 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use super::Movement;
+use crate::dim2::grid::{BoundedGrid, Direction, Grid, Position};
+
+/// distance of every tile to the closest of the given `goals` with 4-way movement where each step costs 1.
+///
+/// uses the same pathable predicate as `get_shortest_path`, unreachable tiles are `None`.
+pub fn get_distance_map<G: BoundedGrid + ?Sized>(grid: &G, goals: &[Position], is_pathable_tile: &dyn Fn(&G::Tile) -> bool) -> Grid<Option<u32>> {
+    let tile_cost = |tile: &G::Tile| is_pathable_tile(tile).then_some(1);
+    get_weighted_distance_map(grid, goals, Movement::Orthogonal, &tile_cost)
+}
+
+/// cost of the cheapest path from every tile to the closest of the given `goals`, unreachable tiles are `None`.
+///
+/// `tile_cost` is described in the `pathing` module documentation.
+/// a single map can be shared by any number of units heading for the same goals.
+pub fn get_weighted_distance_map<G: BoundedGrid + ?Sized>(
+    grid: &G,
+    goals: &[Position],
+    movement: Movement,
+    tile_cost: &dyn Fn(&G::Tile) -> Option<i64>,
+) -> Grid<Option<u32>> {
+    let size = grid.size();
+    let mut distances = Grid::new(size.width, size.height, None).with_topology(grid.topology());
+    let mut open = BinaryHeap::new();
+
+    for &goal in goals {
+        if distances.set(goal, Some(0)).is_ok() {
+            open.push(Reverse((0, goal)));
+        }
+    }
+
+    while let Some(Reverse((distance, node))) = open.pop() {
+        if distances[node].is_some_and(|known| known < distance) {
+            continue;
+        }
+
+        // steps are symmetric, so every tile that can step onto `node` is also reachable from it
+        let Some(node_cost) = grid.get_ref(node).and_then(tile_cost) else {
+            continue;
+        };
+        for (neighbor, _) in movement.neighbors(grid, node, tile_cost) {
+            let neighbor_distance = distance + (node_cost * movement.step_cost(grid.offset(node, neighbor))) as u32;
+            if distances[neighbor].is_none_or(|known| neighbor_distance < known) {
+                distances[neighbor] = Some(neighbor_distance);
+                open.push(Reverse((neighbor_distance, neighbor)));
+            }
+        }
+    }
+
+    distances
+}
+
+/// derives the direction every tile has to step towards to follow the cheapest path along `distance_map`.
+///
+/// goals and unreachable tiles are `None`.
+/// `movement` and `tile_cost` are expected to match the ones the distance map has been created with.
+pub fn get_flow_field<G: BoundedGrid + ?Sized>(
+    grid: &G,
+    distance_map: &Grid<Option<u32>>,
+    movement: Movement,
+    tile_cost: &dyn Fn(&G::Tile) -> Option<i64>,
+) -> Grid<Option<Direction>> {
+    let size = grid.size();
+    let mut flow_field = Grid::new(size.width, size.height, None).with_topology(grid.topology());
+
+    for (position, direction) in flow_field.iter_mut_with_position() {
+        if distance_map.get(position).flatten().is_none_or(|distance| distance == 0) {
+            continue;
+        }
+
+        *direction = movement
+            .neighbors(grid, position, tile_cost)
+            .into_iter()
+            .filter_map(|(neighbor, step_cost)| Some((distance_map.get(neighbor).flatten()? as i64 + step_cost, neighbor)))
+            .min()
+            .and_then(|(_, neighbor)| Direction::from_offset(grid.offset(position, neighbor)));
+    }
+
+    flow_field
+}
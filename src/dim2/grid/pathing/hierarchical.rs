@@ -0,0 +1,395 @@
+use std::collections::{BTreeSet, HashMap};
+
+use pathfinding::prelude::{astar, dijkstra_all};
+
+use super::Movement;
+use crate::dim2::grid::{BoundedGrid, Direction, GridRead, Position, RectSize, Topology};
+
+/// hierarchical path finder (HPA*) for large grids.
+///
+/// the grid is partitioned into square clusters. entrances between neighbouring clusters
+/// and the costs between all entrances of a cluster are precomputed, so long range queries
+/// only search the small abstract graph and refine the result cluster by cluster.
+/// paths are near optimal, but not guaranteed to be the cheapest ones.
+/// the topology of the grid is captured as well, clusters at opposite edges of a wrapping grid are neighbours.
+///
+/// the path finder does not own the grid, every call has to pass the grid and tile costs it has been built with.
+///
+/// # Examples
+/// ```
+/// let mut pathfinder = HierarchicalPathfinder::new(&map, 16, Movement::Orthogonal, &tile_cost);
+/// let (path, cost) = pathfinder.get_path(&map, start, end, &tile_cost).unwrap();
+///
+/// map.set(wall_position, Terrain::Wall).unwrap();
+/// pathfinder.update_tile(&map, wall_position, &tile_cost);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HierarchicalPathfinder {
+    movement: Movement,
+    grid_size: RectSize,
+    topology: Topology,
+    cluster_size: usize,
+    /// amount of clusters horizontally and vertically
+    cluster_count: RectSize,
+    /// tile pairs connecting two neighbouring clusters, keyed by the ordered cluster indices
+    transitions: HashMap<(usize, usize), Vec<(Position, Position)>>,
+    /// abstract nodes of every cluster, each of them is an end of a transition
+    entrances: Vec<BTreeSet<Position>>,
+    /// cheapest paths between entrances of the same cluster that stay inside the cluster
+    intra_edges: HashMap<Position, Vec<(Position, i64)>>,
+}
+
+impl HierarchicalPathfinder {
+    /// partitions `grid` into clusters of `cluster_size` * `cluster_size` tiles and builds the abstract graph.
+    ///
+    /// `tile_cost` is described in the `pathing` module documentation.
+    pub fn new<G: BoundedGrid + ?Sized>(grid: &G, cluster_size: usize, movement: Movement, tile_cost: &dyn Fn(&G::Tile) -> Option<i64>) -> Self {
+        assert!(cluster_size > 0, "cluster size has to be at least 1");
+
+        let grid_size = grid.size();
+        let cluster_count = RectSize::new(grid_size.width.div_ceil(cluster_size), grid_size.height.div_ceil(cluster_size));
+        let mut pathfinder = Self {
+            movement,
+            grid_size,
+            topology: grid.topology(),
+            cluster_size,
+            cluster_count,
+            transitions: HashMap::new(),
+            entrances: vec![BTreeSet::new(); cluster_count.len()],
+            intra_edges: HashMap::new(),
+        };
+
+        let clusters: Vec<_> = (0..cluster_count.len()).collect();
+        pathfinder.rebuild_clusters(grid, &clusters, tile_cost);
+        pathfinder
+    }
+
+    pub const fn movement(&self) -> Movement {
+        self.movement
+    }
+
+    pub const fn cluster_size(&self) -> usize {
+        self.cluster_size
+    }
+
+    /// amount of nodes in the abstract graph
+    pub fn entrance_count(&self) -> usize {
+        self.entrances.iter().map(BTreeSet::len).sum()
+    }
+
+    /// repairs the abstract graph after the tile at `position` has changed, e.g. via `GridWrite::set`.
+    ///
+    /// only the clusters touching the tile and its neighbours are rebuilt.
+    pub fn update_tile<G: GridRead + ?Sized>(&mut self, grid: &G, position: Position, tile_cost: &dyn Fn(&G::Tile) -> Option<i64>) {
+        let mut clusters: Vec<_> = std::iter::once(position)
+            .chain(Direction::ALL.iter().map(|&direction| position.step(direction)))
+            .filter_map(|pos| grid.wrap_position(pos))
+            .map(|pos| self.cluster_index(pos))
+            .collect();
+        clusters.sort_unstable();
+        clusters.dedup();
+
+        self.rebuild_clusters(grid, &clusters, tile_cost);
+    }
+
+    /// finds a path from `start` to `end`, exposing the same result shape as `get_shortest_path`.
+    pub fn get_path<G: GridRead + ?Sized>(
+        &self,
+        grid: &G,
+        start: Position,
+        end: Position,
+        tile_cost: &dyn Fn(&G::Tile) -> Option<i64>,
+    ) -> Option<(Vec<Position>, i64)> {
+        let start = grid.wrap_position(start)?;
+        let end = grid.wrap_position(end)?;
+        if start == end {
+            return Some((vec![start], 0));
+        }
+
+        let start_cluster = self.cluster_index(start);
+        let end_cluster = self.cluster_index(end);
+        if start_cluster == end_cluster {
+            if let Some(result) = self.get_local_path(grid, start_cluster, start, end, tile_cost) {
+                return Some(result);
+            }
+        }
+
+        // temporarily connect start and end to the entrances of their clusters
+        let reached_from_start = self.reach_within_cluster(grid, start_cluster, start, tile_cost);
+        let start_edges: Vec<_> = self.entrances[start_cluster]
+            .iter()
+            .filter_map(|entrance| Some((*entrance, reached_from_start.get(entrance)?.1)))
+            .collect();
+        let end_edges: HashMap<_, _> = self.entrances[end_cluster]
+            .iter()
+            .filter_map(|&entrance| Some((entrance, self.get_local_path(grid, end_cluster, entrance, end, tile_cost)?.1)))
+            .collect();
+
+        let (abstract_path, cost) = astar(
+            &start,
+            |&node| {
+                let mut successors = self.get_abstract_successors(grid, node, tile_cost);
+                if node == start {
+                    successors.extend_from_slice(&start_edges);
+                }
+                if let Some(&cost) = end_edges.get(&node) {
+                    successors.push((end, cost));
+                }
+                successors
+            },
+            |node| self.heuristic(*node, end),
+            |node| *node == end,
+        )?;
+
+        let mut path = vec![start];
+        for window in abstract_path.windows(2) {
+            let (from, to) = (window[0], window[1]);
+            let from_cluster = self.cluster_index(from);
+            if from_cluster != self.cluster_index(to) {
+                path.push(to);
+                continue;
+            }
+
+            let (local_path, _) = self.get_local_path(grid, from_cluster, from, to, tile_cost)?;
+            path.extend_from_slice(&local_path[1..]);
+        }
+
+        Some((path, cost))
+    }
+
+    fn get_abstract_successors<G: GridRead + ?Sized>(&self, grid: &G, node: Position, tile_cost: &dyn Fn(&G::Tile) -> Option<i64>) -> Vec<(Position, i64)> {
+        let mut successors = self.intra_edges.get(&node).cloned().unwrap_or_default();
+
+        let cluster = self.cluster_index(node);
+        for neighbor_cluster in self.neighbor_clusters(cluster) {
+            let Some(transitions) = self.transitions.get(&ordered(cluster, neighbor_cluster)) else {
+                continue;
+            };
+
+            for &(a, b) in transitions {
+                let other = if node == a {
+                    b
+                } else if node == b {
+                    a
+                } else {
+                    continue;
+                };
+                if let Some(cost) = grid.get_ref(other).and_then(tile_cost) {
+                    successors.push((other, cost * self.movement.step_cost(self.topology.offset(node, other, self.grid_size))));
+                }
+            }
+        }
+
+        successors
+    }
+
+    /// `clusters` has to be sorted
+    fn rebuild_clusters<G: GridRead + ?Sized>(&mut self, grid: &G, clusters: &[usize], tile_cost: &dyn Fn(&G::Tile) -> Option<i64>) {
+        for &cluster in clusters {
+            for neighbor_cluster in self.neighbor_clusters(cluster) {
+                if cluster < neighbor_cluster || clusters.binary_search(&neighbor_cluster).is_err() {
+                    let transitions = self.find_transitions(grid, cluster, neighbor_cluster, tile_cost);
+                    self.transitions.insert(ordered(cluster, neighbor_cluster), transitions);
+                }
+            }
+        }
+
+        let mut touched_clusters: Vec<_> = clusters.iter().flat_map(|&cluster| self.neighbor_clusters(cluster)).collect();
+        touched_clusters.extend_from_slice(clusters);
+        touched_clusters.sort_unstable();
+        touched_clusters.dedup();
+
+        for cluster in touched_clusters {
+            let entrances = self.collect_entrances(cluster);
+            if clusters.binary_search(&cluster).is_ok() || entrances != self.entrances[cluster] {
+                self.rebuild_intra_edges(grid, cluster, entrances, tile_cost);
+            }
+        }
+    }
+
+    fn rebuild_intra_edges<G: GridRead + ?Sized>(
+        &mut self,
+        grid: &G,
+        cluster: usize,
+        entrances: BTreeSet<Position>,
+        tile_cost: &dyn Fn(&G::Tile) -> Option<i64>,
+    ) {
+        for entrance in &self.entrances[cluster] {
+            self.intra_edges.remove(entrance);
+        }
+
+        for &entrance in &entrances {
+            let reached = self.reach_within_cluster(grid, cluster, entrance, tile_cost);
+            let edges = entrances
+                .iter()
+                .filter_map(|other| Some((*other, reached.get(other)?.1)))
+                .collect();
+            self.intra_edges.insert(entrance, edges);
+        }
+
+        self.entrances[cluster] = entrances;
+    }
+
+    /// every pair of pathable tiles that connects `cluster` with `other`.
+    /// a run of neighbouring pairs along the border is represented by its middle pair only.
+    fn find_transitions<G: GridRead + ?Sized>(
+        &self,
+        grid: &G,
+        cluster: usize,
+        other: usize,
+        tile_cost: &dyn Fn(&G::Tile) -> Option<i64>,
+    ) -> Vec<(Position, Position)> {
+        let is_pathable = |position: Position| grid.get_ref(position).and_then(tile_cost).is_some();
+        let mut straight_pairs = vec![];
+        let mut transitions = vec![];
+
+        for tile in self.border_tiles(cluster).filter(|&tile| is_pathable(tile)) {
+            for direction in Direction::ORTHOGONAL {
+                let Some(neighbor) = grid.wrap_position(tile.step(direction)) else {
+                    continue;
+                };
+                if self.cluster_index(neighbor) == other && is_pathable(neighbor) {
+                    straight_pairs.push((tile, neighbor));
+                }
+            }
+
+            // connections only possible by cutting a corner are not covered by straight pairs
+            if !self.movement.allows_corner_cutting() {
+                continue;
+            }
+            for direction in Direction::DIAGONAL {
+                let Some(neighbor) = grid.wrap_position(tile.step(direction)) else {
+                    continue;
+                };
+                let offset = direction.offset();
+                if self.cluster_index(neighbor) == other
+                    && is_pathable(neighbor)
+                    && !is_pathable(Position::new(tile.x + offset.x, tile.y))
+                    && !is_pathable(Position::new(tile.x, tile.y + offset.y))
+                {
+                    transitions.push(ordered(tile, neighbor));
+                }
+            }
+        }
+
+        straight_pairs.sort_unstable();
+        let mut run_start = 0;
+        for index in 1..=straight_pairs.len() {
+            let run_continues = index < straight_pairs.len() && {
+                let (previous, current) = (straight_pairs[index - 1].0, straight_pairs[index].0);
+                let step = current - previous;
+                step.x.abs() + step.y.abs() == 1
+            };
+            if !run_continues {
+                transitions.push(ordered_pair(straight_pairs[(run_start + index - 1) / 2]));
+                run_start = index;
+            }
+        }
+
+        transitions
+    }
+
+    fn collect_entrances(&self, cluster: usize) -> BTreeSet<Position> {
+        self.neighbor_clusters(cluster)
+            .filter_map(|neighbor_cluster| self.transitions.get(&ordered(cluster, neighbor_cluster)))
+            .flatten()
+            .flat_map(|&(a, b)| [a, b])
+            .filter(|&position| self.cluster_index(position) == cluster)
+            .collect()
+    }
+
+    /// cheapest costs from `start` to every tile of `cluster` without leaving it
+    fn reach_within_cluster<G: GridRead + ?Sized>(
+        &self,
+        grid: &G,
+        cluster: usize,
+        start: Position,
+        tile_cost: &dyn Fn(&G::Tile) -> Option<i64>,
+    ) -> HashMap<Position, (Position, i64)> {
+        dijkstra_all(&start, |&node| self.get_local_neighbors(grid, cluster, node, tile_cost))
+    }
+
+    fn get_local_path<G: GridRead + ?Sized>(
+        &self,
+        grid: &G,
+        cluster: usize,
+        start: Position,
+        end: Position,
+        tile_cost: &dyn Fn(&G::Tile) -> Option<i64>,
+    ) -> Option<(Vec<Position>, i64)> {
+        astar(
+            &start,
+            |&node| self.get_local_neighbors(grid, cluster, node, tile_cost),
+            |node| self.heuristic(*node, end),
+            |node| *node == end,
+        )
+    }
+
+    fn get_local_neighbors<G: GridRead + ?Sized>(
+        &self,
+        grid: &G,
+        cluster: usize,
+        node: Position,
+        tile_cost: &dyn Fn(&G::Tile) -> Option<i64>,
+    ) -> Vec<(Position, i64)> {
+        let mut neighbors = self.movement.neighbors(grid, node, tile_cost);
+        neighbors.retain(|&(position, _)| self.cluster_index(position) == cluster);
+        neighbors
+    }
+
+    fn heuristic(&self, from: Position, to: Position) -> i64 {
+        self.movement.heuristic(from, to, self.grid_size, self.topology)
+    }
+
+    fn cluster_index(&self, position: Position) -> usize {
+        let cluster_x = position.x as usize / self.cluster_size;
+        let cluster_y = position.y as usize / self.cluster_size;
+        cluster_y * self.cluster_count.width + cluster_x
+    }
+
+    /// top left corner and size of a cluster, clusters at the right and bottom edge may be smaller
+    fn cluster_bounds(&self, cluster: usize) -> (Position, RectSize) {
+        let cluster_x = cluster % self.cluster_count.width;
+        let cluster_y = cluster / self.cluster_count.width;
+        let offset = Position::from((cluster_x * self.cluster_size, cluster_y * self.cluster_size));
+        let width = self.cluster_size.min(self.grid_size.width - offset.x as usize);
+        let height = self.cluster_size.min(self.grid_size.height - offset.y as usize);
+        (offset, RectSize::new(width, height))
+    }
+
+    /// clusters surrounding `cluster`, including diagonal ones, without `cluster` itself
+    fn neighbor_clusters(&self, cluster: usize) -> impl Iterator<Item = usize> {
+        let cluster_position = Position::from((cluster % self.cluster_count.width, cluster / self.cluster_count.width));
+        let mut neighbors: Vec<_> = Direction::ALL
+            .into_iter()
+            .filter_map(|direction| self.topology.wrap(cluster_position.step(direction), self.cluster_count))
+            .map(|neighbor| neighbor.y as usize * self.cluster_count.width + neighbor.x as usize)
+            .filter(|&neighbor| neighbor != cluster)
+            .collect();
+        // wrapping around a grid that is only one or two clusters wide reaches the same cluster from several sides
+        neighbors.sort_unstable();
+        neighbors.dedup();
+        neighbors.into_iter()
+    }
+
+    /// tiles of `cluster` that have a neighbour outside of it
+    fn border_tiles(&self, cluster: usize) -> impl Iterator<Item = Position> {
+        let (offset, size) = self.cluster_bounds(cluster);
+        let last = Position::new(offset.x + size.width as i64 - 1, offset.y + size.height as i64 - 1);
+        (offset.y..=last.y)
+            .flat_map(move |y| (offset.x..=last.x).map(move |x| Position::new(x, y)))
+            .filter(move |tile| tile.x == offset.x || tile.y == offset.y || tile.x == last.x || tile.y == last.y)
+    }
+}
+
+fn ordered<T: Ord>(a: T, b: T) -> (T, T) {
+    if a <= b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+fn ordered_pair<T: Ord>((a, b): (T, T)) -> (T, T) {
+    ordered(a, b)
+}
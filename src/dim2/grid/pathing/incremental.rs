@@ -0,0 +1,220 @@
+use std::collections::{BTreeSet, HashMap};
+
+use super::Movement;
+use crate::dim2::grid::{BoundedGrid, Grid, GridRead, Position, Topology};
+
+const UNREACHABLE: i64 = i64::MAX;
+
+/// priority of a queued tile, compared lexicographically
+type Key = (i64, i64);
+
+/// incremental path planner (D* Lite) bound to a grid, a start and a goal.
+///
+/// instead of searching from scratch after the grid changes, the planner is notified about
+/// changed tiles via `update_tile` and only repairs the affected part of its search.
+/// the start may move along the path via `set_start`, e.g. while a unit follows it.
+///
+/// the planner does not own the grid, every call has to pass the grid and tile costs it has been created with.
+///
+/// # Examples
+/// ```
+/// let mut planner = IncrementalPathfinder::new(&map, creep.position, base, Movement::Orthogonal, &tile_cost);
+///
+/// map.set(tower_position, Tile::Tower).unwrap();
+/// planner.update_tile(&map, tower_position, &tile_cost);
+/// let (path, cost) = planner.get_path(&map, &tile_cost).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct IncrementalPathfinder {
+    movement: Movement,
+    topology: Topology,
+    start: Position,
+    goal: Position,
+    /// start at the time of the last repair, used to keep queued keys valid after the start moved
+    last_start: Position,
+    key_modifier: i64,
+    /// cost from every tile to the goal as of the last expansion
+    distances: Grid<i64>,
+    /// one step lookahead of `distances`
+    lookahead: Grid<i64>,
+    queue: BTreeSet<(Key, Position)>,
+    queued: HashMap<Position, Key>,
+}
+
+impl IncrementalPathfinder {
+    /// plans the initial path from `start` to `goal`.
+    ///
+    /// `tile_cost` is described in the `pathing` module documentation.
+    pub fn new<G: BoundedGrid + ?Sized>(grid: &G, start: Position, goal: Position, movement: Movement, tile_cost: &dyn Fn(&G::Tile) -> Option<i64>) -> Self {
+        let start = grid.wrap_position(start).unwrap_or(start);
+        let goal = grid.wrap_position(goal).unwrap_or(goal);
+        let size = grid.size();
+        let mut pathfinder = Self {
+            movement,
+            topology: grid.topology(),
+            start,
+            goal,
+            last_start: start,
+            key_modifier: 0,
+            distances: Grid::new(size.width, size.height, UNREACHABLE),
+            lookahead: Grid::new(size.width, size.height, UNREACHABLE),
+            queue: BTreeSet::new(),
+            queued: HashMap::new(),
+        };
+
+        if pathfinder.lookahead.set(goal, 0).is_ok() {
+            pathfinder.enqueue(goal);
+        }
+        pathfinder.compute_shortest_path(grid, tile_cost);
+        pathfinder
+    }
+
+    pub const fn start(&self) -> Position {
+        self.start
+    }
+
+    pub const fn goal(&self) -> Position {
+        self.goal
+    }
+
+    pub const fn movement(&self) -> Movement {
+        self.movement
+    }
+
+    /// moves the start, e.g. after the unit took a step along the path
+    pub fn set_start<G: GridRead + ?Sized>(&mut self, grid: &G, start: Position, tile_cost: &dyn Fn(&G::Tile) -> Option<i64>) {
+        self.start = grid.wrap_position(start).unwrap_or(start);
+        self.update_tiles(grid, &[], tile_cost);
+    }
+
+    /// repairs the plan after the tile at `position` has changed, e.g. via `GridWrite::set`
+    pub fn update_tile<G: GridRead + ?Sized>(&mut self, grid: &G, position: Position, tile_cost: &dyn Fn(&G::Tile) -> Option<i64>) {
+        self.update_tiles(grid, &[position], tile_cost);
+    }
+
+    /// repairs the plan after all tiles in `positions` have changed
+    pub fn update_tiles<G: GridRead + ?Sized>(&mut self, grid: &G, positions: &[Position], tile_cost: &dyn Fn(&G::Tile) -> Option<i64>) {
+        self.key_modifier += self.heuristic(self.last_start, self.start);
+        self.last_start = self.start;
+
+        // the cost of entering a tile and the corner cutting rules only affect its direct neighbours
+        for &position in positions {
+            self.update_vertex(grid, position, tile_cost);
+            for &neighbor in self.movement.adjacent_positions(position).iter() {
+                self.update_vertex(grid, neighbor, tile_cost);
+            }
+        }
+        self.compute_shortest_path(grid, tile_cost);
+    }
+
+    /// cost of the current path from the start to the goal, `None` if the goal is unreachable
+    pub fn cost(&self) -> Option<i64> {
+        self.distances.get(self.start).filter(|&distance| distance != UNREACHABLE)
+    }
+
+    /// the current shortest path, exposing the same result shape as `get_shortest_path`
+    pub fn get_path<G: GridRead + ?Sized>(&self, grid: &G, tile_cost: &dyn Fn(&G::Tile) -> Option<i64>) -> Option<(Vec<Position>, i64)> {
+        self.cost()?;
+
+        let mut path = vec![self.start];
+        let mut cost = 0;
+        let mut current = self.start;
+        while current != self.goal {
+            if path.len() > self.distances.len() {
+                return None;
+            }
+
+            let (next, step_cost) = self
+                .movement
+                .neighbors(grid, current, tile_cost)
+                .into_iter()
+                .min_by_key(|&(neighbor, step_cost)| self.distances[neighbor].saturating_add(step_cost))?;
+            if self.distances[next] == UNREACHABLE {
+                return None;
+            }
+
+            cost += step_cost;
+            current = next;
+            path.push(current);
+        }
+
+        Some((path, cost))
+    }
+
+    fn compute_shortest_path<G: GridRead + ?Sized>(&mut self, grid: &G, tile_cost: &dyn Fn(&G::Tile) -> Option<i64>) {
+        let Some(mut start_distance) = self.distances.get(self.start) else {
+            return;
+        };
+
+        while let Some(&(old_key, node)) = self.queue.first() {
+            if old_key >= self.calculate_key(self.start) && self.lookahead[self.start] == start_distance {
+                break;
+            }
+
+            let new_key = self.calculate_key(node);
+            if old_key < new_key {
+                self.dequeue(node);
+                self.enqueue(node);
+            } else if self.distances[node] > self.lookahead[node] {
+                self.distances[node] = self.lookahead[node];
+                self.dequeue(node);
+                for &neighbor in self.movement.adjacent_positions(node).iter() {
+                    self.update_vertex(grid, neighbor, tile_cost);
+                }
+            } else {
+                self.distances[node] = UNREACHABLE;
+                self.update_vertex(grid, node, tile_cost);
+                for &neighbor in self.movement.adjacent_positions(node).iter() {
+                    self.update_vertex(grid, neighbor, tile_cost);
+                }
+            }
+
+            start_distance = self.distances[self.start];
+        }
+    }
+
+    fn update_vertex<G: GridRead + ?Sized>(&mut self, grid: &G, node: Position, tile_cost: &dyn Fn(&G::Tile) -> Option<i64>) {
+        let Some(node) = grid.wrap_position(node) else {
+            return;
+        };
+
+        if node != self.goal {
+            self.lookahead[node] = self
+                .movement
+                .neighbors(grid, node, tile_cost)
+                .into_iter()
+                .map(|(neighbor, step_cost)| self.distances[neighbor].saturating_add(step_cost))
+                .min()
+                .unwrap_or(UNREACHABLE);
+        }
+
+        self.dequeue(node);
+        if self.distances[node] != self.lookahead[node] {
+            self.enqueue(node);
+        }
+    }
+
+    fn calculate_key(&self, node: Position) -> Key {
+        let distance = self.distances[node].min(self.lookahead[node]);
+        let estimate = distance
+            .saturating_add(self.heuristic(self.start, node))
+            .saturating_add(self.key_modifier);
+        (estimate, distance)
+    }
+
+    fn heuristic(&self, from: Position, to: Position) -> i64 {
+        self.movement.heuristic(from, to, self.distances.size(), self.topology)
+    }
+
+    fn enqueue(&mut self, node: Position) {
+        let key = self.calculate_key(node);
+        self.queue.insert((key, node));
+        self.queued.insert(node, key);
+    }
+
+    fn dequeue(&mut self, node: Position) {
+        if let Some(key) = self.queued.remove(&node) {
+            self.queue.remove(&(key, node));
+        }
+    }
+}
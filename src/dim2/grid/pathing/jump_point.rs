@@ -0,0 +1,167 @@
+use pathfinding::prelude::astar;
+
+use super::{search_area, Movement};
+use crate::dim2::grid::{GridRead, Position};
+
+/// movement rules jump point search is optimal for
+const MOVEMENT: Movement = Movement::Octile { corner_cutting: false };
+
+/// finds the shortest path with jump point search, taking the same inputs as `get_shortest_path`.
+///
+/// movement is 8-way without cutting corners and every tile costs the same, so the result is
+/// identical in cost to `get_shortest_weighted_path` with `Movement::Octile { corner_cutting: false }`
+/// and a tile cost of 1. instead of expanding every tile, straight and diagonal runs without
+/// any decisions along them are skipped, which is much faster on open maps.
+/// the returned path contains every tile, not only the jump points.
+/// on a wrapping grid, runs continue across the seams.
+/// on an unbounded grid the search is limited to `UNBOUNDED_SEARCH_MARGIN` tiles around `start` and `end`.
+pub fn get_jump_point_path<G: GridRead + ?Sized>(
+    grid: &G,
+    start: Position,
+    end: Position,
+    is_pathable_tile: &dyn Fn(&G::Tile) -> bool,
+) -> Option<(Vec<Position>, i64)> {
+    let start = grid.wrap_position(start).unwrap_or(start);
+    let end = grid.wrap_position(end).unwrap_or(end);
+    let in_search_area = search_area(grid, start, end);
+    let search = JumpPointSearch {
+        grid,
+        end,
+        is_pathable_tile,
+        in_search_area: &in_search_area,
+    };
+
+    // nodes are jump points paired with the direction they were entered from
+    let (jump_points, cost) = astar(
+        &(start, Position::ZERO),
+        |&(node, direction)| search.successors(node, direction),
+        |&(node, _)| MOVEMENT.estimate(grid.offset(node, end)),
+        |&(node, _)| node == end,
+    )?;
+
+    let mut path = vec![start];
+    for window in jump_points.windows(2) {
+        let ((from, _), (to, direction)) = (window[0], window[1]);
+        let mut current = from;
+        while current != to {
+            current = search.wrap(current + direction);
+            path.push(current);
+        }
+    }
+
+    Some((path, cost))
+}
+
+struct JumpPointSearch<'a, G: GridRead + ?Sized> {
+    grid: &'a G,
+    end: Position,
+    is_pathable_tile: &'a dyn Fn(&G::Tile) -> bool,
+    /// tiles outside of the search area count as blocked, so jumps through open space end at its border
+    in_search_area: &'a dyn Fn(Position) -> bool,
+}
+
+impl<G: GridRead + ?Sized> JumpPointSearch<'_, G> {
+    fn successors(&self, node: Position, direction: Position) -> Vec<((Position, Position), i64)> {
+        self.pruned_directions(node, direction)
+            .into_iter()
+            .filter_map(|direction| {
+                let jump_point = self.jump(node + direction, direction)?;
+                Some(((self.wrap(jump_point), direction), MOVEMENT.estimate(jump_point - node)))
+            })
+            .collect()
+    }
+
+    /// directions worth exploring from `node` when it has been entered moving along `direction`
+    fn pruned_directions(&self, node: Position, direction: Position) -> Vec<Position> {
+        let (dx, dy) = (direction.x, direction.y);
+        let open = |x: i64, y: i64| self.is_pathable(Position::new(node.x + x, node.y + y));
+        let mut directions = vec![];
+
+        if direction == Position::ZERO {
+            for y in -1..=1 {
+                for x in -1..=1 {
+                    let diagonal_allowed = x == 0 || y == 0 || (open(x, 0) && open(0, y));
+                    if (x, y) != (0, 0) && open(x, y) && diagonal_allowed {
+                        directions.push(Position::new(x, y));
+                    }
+                }
+            }
+        } else if dx != 0 && dy != 0 {
+            if open(0, dy) {
+                directions.push(Position::new(0, dy));
+            }
+            if open(dx, 0) {
+                directions.push(Position::new(dx, 0));
+            }
+            if open(0, dy) && open(dx, 0) && open(dx, dy) {
+                directions.push(direction);
+            }
+        } else {
+            // perpendicular directions relative to the movement
+            let (left, right) = (Position::new(dy, dx), Position::new(-dy, -dx));
+            let forward_open = open(dx, dy);
+            if forward_open {
+                directions.push(direction);
+            }
+            for side in [left, right] {
+                if open(side.x, side.y) {
+                    directions.push(side);
+                    if forward_open && open(dx + side.x, dy + side.y) {
+                        directions.push(direction + side);
+                    }
+                }
+            }
+        }
+
+        directions
+    }
+
+    /// follows `direction` from `position` until a jump point is found.
+    ///
+    /// the jump point is not wrapped, so its distance to `position` stays intact.
+    fn jump(&self, mut position: Position, direction: Position) -> Option<Position> {
+        let (dx, dy) = (direction.x, direction.y);
+        let first = self.wrap(position);
+        loop {
+            if !self.is_pathable(position) {
+                return None;
+            }
+            if self.wrap(position) == self.end {
+                return Some(position);
+            }
+
+            let open = |x: i64, y: i64| self.is_pathable(Position::new(position.x + x, position.y + y));
+            if dx != 0 && dy != 0 {
+                if self.jump(position + Position::new(dx, 0), Position::new(dx, 0)).is_some()
+                    || self.jump(position + Position::new(0, dy), Position::new(0, dy)).is_some()
+                {
+                    return Some(position);
+                }
+            } else if dx != 0 {
+                if (open(0, -1) && !open(-dx, -1)) || (open(0, 1) && !open(-dx, 1)) {
+                    return Some(position);
+                }
+            } else if (open(-1, 0) && !open(-1, -dy)) || (open(1, 0) && !open(1, -dy)) {
+                return Some(position);
+            }
+
+            // no corner cutting, both orthogonal neighbours have to be open to move on
+            if !open(dx, 0) || !open(0, dy) {
+                return None;
+            }
+            position += direction;
+            // went all the way around a wrapping grid without finding anything
+            if self.wrap(position) == first {
+                return None;
+            }
+        }
+    }
+
+    fn is_pathable(&self, position: Position) -> bool {
+        (self.in_search_area)(position) && self.grid.get_ref(position).is_some_and(self.is_pathable_tile)
+    }
+
+    fn wrap(&self, position: Position) -> Position {
+        self.grid.wrap_position(position).unwrap_or(position)
+    }
+}
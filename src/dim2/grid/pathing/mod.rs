@@ -0,0 +1,92 @@
+//! path finding on grids.
+//!
+//! algorithms with weighted movement take a `tile_cost` callback. it receives the tile that is about to be entered
+//! and returns the cost of entering it, or `None` if the tile is not pathable.
+
+use pathfinding::prelude::astar;
+
+use super::{position::Position, GridRead};
+
+pub mod distance_map;
+pub use distance_map::*;
+
+pub mod hierarchical;
+pub use hierarchical::*;
+
+pub mod incremental;
+pub use incremental::*;
+
+pub mod jump_point;
+pub use jump_point::*;
+
+pub mod movement;
+pub use movement::*;
+
+pub mod reachable;
+pub use reachable::*;
+
+/// on an unbounded grid, searches between two positions only expand tiles at most this far outside of the rectangle
+/// spanned by them. if the end can't be reached inside of that area the search returns `None` instead of running forever.
+pub const UNBOUNDED_SEARCH_MARGIN: i64 = 64;
+
+pub fn path_exists<G: GridRead + ?Sized>(grid: &G, start: Position, end: Position, is_pathable_tile: &dyn Fn(&G::Tile) -> bool) -> bool {
+    get_shortest_path(grid, start, end, is_pathable_tile).is_some()
+}
+
+pub fn get_shortest_path<G: GridRead + ?Sized>(
+    grid: &G,
+    start: Position,
+    end: Position,
+    is_pathable_tile: &dyn Fn(&G::Tile) -> bool,
+) -> Option<(Vec<Position>, i64)> {
+    let tile_cost = |tile: &G::Tile| is_pathable_tile(tile).then_some(1);
+    get_shortest_weighted_path(grid, start, end, Movement::Orthogonal, &tile_cost)
+}
+
+/// finds the cheapest path from `start` to `end` according to the given `movement` rules.
+///
+/// `tile_cost` is described in the module documentation.
+/// costs are expected to be at least 1 for the heuristic to stay admissible.
+/// works on every grid backend, on a wrapping grid, see `Grid::with_topology`, paths may cross the seams.
+/// on an unbounded grid the search is limited to `UNBOUNDED_SEARCH_MARGIN` tiles around `start` and `end`.
+///
+/// # Examples
+/// ```
+/// // roads cost 1, swamps cost 3, walls can't be entered
+/// let tile_cost = |tile: &Terrain| match tile {
+///     Terrain::Road => Some(1),
+///     Terrain::Swamp => Some(3),
+///     Terrain::Wall => None,
+/// };
+/// let movement = Movement::Octile { corner_cutting: false };
+/// let (path, cost) = get_shortest_weighted_path(&grid, start, end, movement, &tile_cost).unwrap();
+/// ```
+pub fn get_shortest_weighted_path<G: GridRead + ?Sized>(
+    grid: &G,
+    start: Position,
+    end: Position,
+    movement: Movement,
+    tile_cost: &dyn Fn(&G::Tile) -> Option<i64>,
+) -> Option<(Vec<Position>, i64)> {
+    let start = grid.wrap_position(start).unwrap_or(start);
+    let end = grid.wrap_position(end).unwrap_or(end);
+    let in_search_area = search_area(grid, start, end);
+    astar(
+        &start,
+        |node| {
+            let mut neighbors = movement.neighbors(grid, *node, tile_cost);
+            neighbors.retain(|(neighbor, _)| in_search_area(*neighbor));
+            neighbors
+        },
+        |node| movement.estimate(grid.offset(*node, end)),
+        |node| *node == end,
+    )
+}
+
+/// whether a search from `start` to `end` may expand a position, see `UNBOUNDED_SEARCH_MARGIN`
+pub(crate) fn search_area<G: GridRead + ?Sized>(grid: &G, start: Position, end: Position) -> impl Fn(Position) -> bool {
+    let is_bounded = grid.bounds().is_some();
+    let min = Position::new(start.x.min(end.x).saturating_sub(UNBOUNDED_SEARCH_MARGIN), start.y.min(end.y).saturating_sub(UNBOUNDED_SEARCH_MARGIN));
+    let max = Position::new(start.x.max(end.x).saturating_add(UNBOUNDED_SEARCH_MARGIN), start.y.max(end.y).saturating_add(UNBOUNDED_SEARCH_MARGIN));
+    move |position| is_bounded || ((min.x..=max.x).contains(&position.x) && (min.y..=max.y).contains(&position.y))
+}
@@ -0,0 +1,103 @@
+use crate::dim2::grid::{
+    patterns::{adjacent_pattern, square_pattern, PatternPositions},
+    GridRead, Position, RectSize, Topology,
+};
+
+/// cost factor of a straight step in `Movement::Octile` mode
+pub const OCTILE_STRAIGHT_COST: i64 = 10;
+/// cost factor of a diagonal step in `Movement::Octile` mode, approximates √2 * `OCTILE_STRAIGHT_COST`
+pub const OCTILE_DIAGONAL_COST: i64 = 14;
+
+/// movement rules used by the weighted path finding functions.
+///
+/// every mode comes with its own heuristic that stays admissible as long as
+/// every tile costs at least 1 to enter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Movement {
+    /// 4-way movement, estimated by the Manhattan distance
+    #[default]
+    Orthogonal,
+    /// 8-way movement where a diagonal step costs the same as a straight one, estimated by the Chebyshev distance
+    Diagonal { corner_cutting: bool },
+    /// 8-way movement where a diagonal step costs roughly √2 times a straight one, estimated by the octile distance.
+    /// step costs are scaled by `OCTILE_STRAIGHT_COST` and `OCTILE_DIAGONAL_COST` respectively.
+    Octile { corner_cutting: bool },
+}
+
+impl Movement {
+    pub const fn allows_diagonals(&self) -> bool {
+        !matches!(self, Self::Orthogonal)
+    }
+
+    /// whether a diagonal step may squeeze past a blocked orthogonal neighbour
+    pub const fn allows_corner_cutting(&self) -> bool {
+        match self {
+            Self::Orthogonal => false,
+            Self::Diagonal { corner_cutting } | Self::Octile { corner_cutting } => *corner_cutting,
+        }
+    }
+
+    /// lower bound of the cost to get from `from` to `to` on a grid of `size` assuming every tile costs 1,
+    /// paths may cross the seams of a wrapping `topology`
+    pub fn heuristic(&self, from: Position, to: Position, size: RectSize, topology: Topology) -> i64 {
+        self.estimate(topology.offset(from, to, size))
+    }
+
+    /// lower bound of the cost to move by `offset` assuming every tile costs 1
+    pub(crate) fn estimate(&self, offset: Position) -> i64 {
+        let dx = offset.x.abs();
+        let dy = offset.y.abs();
+        match self {
+            Self::Orthogonal => dx + dy,
+            Self::Diagonal { .. } => dx.max(dy),
+            Self::Octile { .. } => OCTILE_STRAIGHT_COST * dx.max(dy) + (OCTILE_DIAGONAL_COST - OCTILE_STRAIGHT_COST) * dx.min(dy),
+        }
+    }
+
+    /// cost factor of a single step by `offset`, which is expected to point to an adjacent tile
+    pub fn step_cost(&self, offset: Position) -> i64 {
+        let diagonal = offset.x != 0 && offset.y != 0;
+        match (self, diagonal) {
+            (Self::Octile { .. }, true) => OCTILE_DIAGONAL_COST,
+            (Self::Octile { .. }, false) => OCTILE_STRAIGHT_COST,
+            _ => 1,
+        }
+    }
+
+    /// all positions next to `node`, regardless of whether they can be entered
+    pub fn adjacent_positions(&self, node: Position) -> Box<[Position]> {
+        if self.allows_diagonals() {
+            square_pattern(1).get_pattern_positions(node, false)
+        } else {
+            adjacent_pattern().get_pattern_positions(node, false)
+        }
+    }
+
+    /// every neighbour of `node` that can be entered, paired with the cost of stepping onto it.
+    ///
+    /// `tile_cost` is described in the `pathing` module documentation.
+    /// neighbours across the seams of a wrapping grid are returned as their position inside of the grid.
+    pub fn neighbors<G: GridRead + ?Sized>(&self, grid: &G, node: Position, tile_cost: &dyn Fn(&G::Tile) -> Option<i64>) -> Vec<(Position, i64)> {
+        self.adjacent_positions(node)
+            .iter()
+            .filter_map(|&pos| {
+                let offset = pos - node;
+                if offset.x != 0 && offset.y != 0 && !self.allows_corner_cutting() {
+                    let horizontal = Position::new(node.x + offset.x, node.y);
+                    let vertical = Position::new(node.x, node.y + offset.y);
+                    if !is_pathable(grid, horizontal, tile_cost) || !is_pathable(grid, vertical, tile_cost) {
+                        return None;
+                    }
+                }
+
+                let pos = grid.wrap_position(pos)?;
+                let cost = tile_cost(grid.get_ref(pos)?)?;
+                Some((pos, cost * self.step_cost(offset)))
+            })
+            .collect()
+    }
+}
+
+fn is_pathable<G: GridRead + ?Sized>(grid: &G, position: Position, tile_cost: &dyn Fn(&G::Tile) -> Option<i64>) -> bool {
+    grid.get_ref(position).and_then(tile_cost).is_some()
+}
@@ -0,0 +1,114 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+};
+
+use super::Movement;
+use crate::dim2::grid::{GridRead, Position};
+
+/// every tile a unit can reach within its movement budget, see `get_reachable_tiles`
+#[derive(Debug, Clone)]
+pub struct ReachableTiles {
+    start: Position,
+    /// cheapest cost to reach a tile and the tile it is entered from
+    tiles: HashMap<Position, (i64, Position)>,
+}
+
+impl ReachableTiles {
+    pub const fn start(&self) -> Position {
+        self.start
+    }
+
+    /// amount of reachable tiles, including the start
+    pub fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        self.tiles.contains_key(&position)
+    }
+
+    /// cost of the cheapest path to `position` or `None` if it is out of reach
+    pub fn cost(&self, position: Position) -> Option<i64> {
+        self.tiles.get(&position).map(|&(cost, _)| cost)
+    }
+
+    /// cheapest path from the start to `position`, including both ends
+    pub fn path_to(&self, position: Position) -> Option<Vec<Position>> {
+        self.tiles.get(&position)?;
+
+        let mut path = vec![position];
+        let mut current = position;
+        while current != self.start {
+            current = self.tiles[&current].1;
+            path.push(current);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// all reachable positions, in no particular order
+    pub fn positions(&self) -> impl Iterator<Item = Position> + '_ {
+        self.tiles.keys().copied()
+    }
+
+    /// all reachable positions paired with their cost, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Position, i64)> + '_ {
+        self.tiles.iter().map(|(&position, &(cost, _))| (position, cost))
+    }
+}
+
+/// collects every tile that can be reached from `start` without spending more than `budget`.
+///
+/// `tile_cost` is described in the `pathing` module documentation.
+/// the start is always reachable at a cost of 0, even if it is not pathable itself.
+///
+/// # Examples
+/// ```
+/// // highlight where the selected unit can move this turn
+/// let reachable = get_reachable_tiles(&map, unit.position, unit.movement_points, Movement::Orthogonal, &tile_cost);
+/// for position in reachable.positions() {
+///     highlight(position);
+/// }
+/// let path = reachable.path_to(clicked_position);
+/// ```
+pub fn get_reachable_tiles<G: GridRead + ?Sized>(
+    grid: &G,
+    start: Position,
+    budget: i64,
+    movement: Movement,
+    tile_cost: &dyn Fn(&G::Tile) -> Option<i64>,
+) -> ReachableTiles {
+    let mut tiles = HashMap::new();
+    let mut open = BinaryHeap::new();
+
+    let start = grid.wrap_position(start).unwrap_or(start);
+    if grid.get_ref(start).is_some() && budget >= 0 {
+        tiles.insert(start, (0, start));
+        open.push(Reverse((0, start)));
+    }
+
+    while let Some(Reverse((cost, node))) = open.pop() {
+        if tiles[&node].0 < cost {
+            continue;
+        }
+
+        for (neighbor, step_cost) in movement.neighbors(grid, node, tile_cost) {
+            let neighbor_cost = cost + step_cost;
+            if neighbor_cost > budget {
+                continue;
+            }
+
+            if tiles.get(&neighbor).is_none_or(|&(known, _)| neighbor_cost < known) {
+                tiles.insert(neighbor, (neighbor_cost, node));
+                open.push(Reverse((neighbor_cost, neighbor)));
+            }
+        }
+    }
+
+    ReachableTiles { start, tiles }
+}
@@ -1,14 +1,15 @@
 use std::{cell::OnceCell, collections::BTreeMap, sync::Arc};
 
 use crate::{
-    dim2::grid::{patterns::GridPattern, Grid, Position},
+    dim2::grid::{
+        patterns::{GridPattern, RectanglePatternCache},
+        Grid, Position,
+    },
     FactoryCache,
 };
 
 pub fn empty_rectangle_pattern(offset_left: usize, offset_up: usize, offset_right: usize, offset_down: usize) -> Arc<GridPattern> {
-    static mut PATTERN_CACHE: OnceCell<
-        FactoryCache<(usize, usize, usize, usize), GridPattern, BTreeMap<(usize, usize, usize, usize), Arc<GridPattern>>>,
-    > = OnceCell::new();
+    static mut PATTERN_CACHE: OnceCell<RectanglePatternCache> = OnceCell::new();
 
     let cache = unsafe {
         #[allow(static_mut_refs)]
@@ -1,11 +1,46 @@
-use super::PatternPositions;
-use crate::dim2::grid::{Grid, Position};
+use super::{PatternPositions, PatternPositionsIter};
+use crate::dim2::grid::{Direction, Grid, Position, Rotation};
 
 pub struct GridPattern {
     pub mapping: Grid<bool>,
     pub center: Position,
 }
 
+impl GridPattern {
+    pub fn rotated(&self, rotation: Rotation) -> GridPattern {
+        GridPattern {
+            mapping: self.mapping.rotated(rotation),
+            center: rotation.rotate_position(self.center, self.mapping.size()),
+        }
+    }
+
+    pub fn flipped_horizontally(&self) -> GridPattern {
+        GridPattern {
+            mapping: self.mapping.flipped_horizontally(),
+            center: Position::new(self.mapping.width() as i64 - 1 - self.center.x, self.center.y),
+        }
+    }
+
+    pub fn flipped_vertically(&self) -> GridPattern {
+        GridPattern {
+            mapping: self.mapping.flipped_vertically(),
+            center: Position::new(self.center.x, self.mapping.height() as i64 - 1 - self.center.y),
+        }
+    }
+
+    pub fn transposed(&self) -> GridPattern {
+        GridPattern {
+            mapping: self.mapping.transposed(),
+            center: Position::new(self.center.y, self.center.x),
+        }
+    }
+
+    /// orients a pattern defined facing `Direction::Up` towards `direction`, `None` for diagonal directions
+    pub fn facing(&self, direction: Direction) -> Option<GridPattern> {
+        Some(self.rotated(Rotation::from_up_to(direction)?))
+    }
+}
+
 impl PatternPositions for GridPattern {
     fn get_pattern_positions(&self, center: Position, fill_center: bool) -> Box<[Position]> {
         let mut result = vec![];
@@ -21,11 +56,23 @@ impl PatternPositions for GridPattern {
 
         result.into_boxed_slice()
     }
+
+    fn iter_pattern_positions(&self, center: Position, fill_center: bool) -> PatternPositionsIter<'_> {
+        PatternPositionsIter::Mapping {
+            mapping: self.mapping.iter_with_position(),
+            offset: center - self.center,
+            center,
+            fill_center,
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::dim2::grid::patterns::{adjacent_pattern, PatternPositions};
+    use crate::dim2::grid::{
+        patterns::{adjacent_pattern, new_rectangle_pattern, PatternPositions},
+        Direction, Position,
+    };
 
     #[test]
     fn empty_center() {
@@ -35,4 +82,27 @@ mod tests {
         let positions = pattern.get_pattern_positions(center, false);
         assert!(!positions.contains(&center));
     }
+
+    #[test]
+    fn facing() {
+        // cone in front of the center
+        let pattern = new_rectangle_pattern(1, 2, 1, 0);
+        let center = Position::new(5, 5);
+
+        let mut positions = pattern.facing(Direction::Right).unwrap().get_pattern_positions(center, false).to_vec();
+        positions.sort();
+        let mut expected = [(6, 4), (6, 5), (6, 6), (7, 4), (7, 5), (7, 6), (5, 4), (5, 6)].map(Position::from).to_vec();
+        expected.sort();
+        assert_eq!(positions, expected);
+
+        assert!(pattern.facing(Direction::UpLeft).is_none());
+    }
+
+    #[test]
+    fn flipped_center() {
+        let pattern = new_rectangle_pattern(2, 0, 0, 1);
+        assert_eq!(pattern.flipped_horizontally().center, Position::new(0, 0));
+        assert_eq!(pattern.flipped_vertically().center, Position::new(2, 1));
+        assert_eq!(pattern.transposed().center, Position::new(0, 2));
+    }
 }
@@ -19,20 +19,61 @@ pub use empty_rectangle_pattern::*;
 pub mod square_pattern;
 pub use square_pattern::*;
 
-use super::{Grid, Position};
+use std::{collections::BTreeMap, sync::Arc, vec};
+
+use super::{grid_iter::GridIter, GridRead, Position};
+use crate::FactoryCache;
+
+/// cache for patterns that are parameterized by their offsets to the left, up, right and down
+pub(crate) type RectanglePatternCache =
+    FactoryCache<(usize, usize, usize, usize), GridPattern, BTreeMap<(usize, usize, usize, usize), Arc<GridPattern>>>;
 
 pub trait PatternPositions {
     fn get_pattern_positions(&self, center: Position, fill_center: bool) -> Box<[Position]>;
+
+    /// same positions as `get_pattern_positions`, patterns backed by a mapping iterate them without allocating
+    fn iter_pattern_positions(&self, center: Position, fill_center: bool) -> PatternPositionsIter<'_> {
+        PatternPositionsIter::Positions(self.get_pattern_positions(center, fill_center).into_vec().into_iter())
+    }
+}
+
+pub enum PatternPositionsIter<'a> {
+    Mapping {
+        mapping: GridIter<'a, bool>,
+        offset: Position,
+        center: Position,
+        fill_center: bool,
+    },
+    Positions(vec::IntoIter<Position>),
+}
+
+impl Iterator for PatternPositionsIter<'_> {
+    type Item = Position;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            PatternPositionsIter::Mapping {
+                mapping,
+                offset,
+                center,
+                fill_center,
+            } => mapping.find_map(|(mapping_pos, &matches)| {
+                let pos = mapping_pos + *offset;
+                (matches && (*fill_center || pos != *center)).then_some(pos)
+            }),
+            PatternPositionsIter::Positions(positions) => positions.next(),
+        }
+    }
 }
 
-pub fn get_grid_values_from_pattern<T>(grid: &Grid<T>, center: Position, fill_center: bool, pattern: &dyn PatternPositions) -> Box<[T]>
+pub fn get_grid_values_from_pattern<G>(grid: &G, center: Position, fill_center: bool, pattern: &dyn PatternPositions) -> Box<[G::Tile]>
 where
-    T: Copy,
+    G: GridRead + ?Sized,
+    G::Tile: Clone,
 {
     pattern
-        .get_pattern_positions(center, fill_center)
-        .iter()
-        .filter_map(|&pos| grid.get(pos))
+        .iter_pattern_positions(center, fill_center)
+        .filter_map(|pos| grid.get(pos))
         .collect::<Vec<_>>()
         .into_boxed_slice()
 }
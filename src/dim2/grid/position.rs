@@ -1,5 +1,10 @@
 use std::ops::{Add, AddAssign, Sub, SubAssign};
 
+use super::{
+    line::{BresenhamLine, SupercoverLine},
+    Direction,
+};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Position {
     pub x: i64,
@@ -21,6 +26,21 @@ impl Position {
     pub fn distance(&self, other: Position) -> f64 {
         distance(*self, other)
     }
+
+    /// all positions on the straight line to `other`, see `BresenhamLine`
+    pub fn line_to(&self, other: Position) -> BresenhamLine {
+        BresenhamLine::new(*self, other)
+    }
+
+    /// all positions touched by the straight line to `other`, see `SupercoverLine`
+    pub fn supercover_line_to(&self, other: Position) -> SupercoverLine {
+        SupercoverLine::new(*self, other)
+    }
+
+    /// the adjacent position in the given direction
+    pub fn step(&self, direction: Direction) -> Position {
+        *self + direction.offset()
+    }
 }
 
 impl Default for Position {
@@ -1,7 +1,7 @@
 use super::position::Position;
 
 /// logical, rectangular size in 2D space
-#[derive(Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct RectSize {
     pub width: usize,
     pub height: usize,
@@ -57,7 +57,7 @@ impl<'a> Iterator for SizeIter<'a> {
     type Item = Position;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.next_coords.y as usize >= self.size.height {
+        if self.next_coords.y as usize >= self.size.height || self.size.width == 0 {
             return None;
         }
 
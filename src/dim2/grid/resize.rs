@@ -0,0 +1,102 @@
+use super::{to_grid_index, Grid, Position, RectSize};
+
+/// the part of a grid that keeps its place when the grid is resized
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Anchor {
+    #[default]
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl Anchor {
+    /// offset of the old content inside the resized grid, negative when the content is cut off
+    pub fn offset(&self, old_size: RectSize, new_size: RectSize) -> Position {
+        let dx = new_size.width as i64 - old_size.width as i64;
+        let dy = new_size.height as i64 - old_size.height as i64;
+        let x = match self {
+            Anchor::TopLeft | Anchor::Left | Anchor::BottomLeft => 0,
+            Anchor::Top | Anchor::Center | Anchor::Bottom => dx.div_euclid(2),
+            Anchor::TopRight | Anchor::Right | Anchor::BottomRight => dx,
+        };
+        let y = match self {
+            Anchor::TopLeft | Anchor::Top | Anchor::TopRight => 0,
+            Anchor::Left | Anchor::Center | Anchor::Right => dy.div_euclid(2),
+            Anchor::BottomLeft | Anchor::Bottom | Anchor::BottomRight => dy,
+        };
+        Position::new(x, y)
+    }
+}
+
+impl<T> Grid<T> {
+    /// grows or shrinks the grid to `new_size`, keeping the content aligned to `anchor`.
+    ///
+    /// new tiles are set to `fill`, tiles outside of the new size are dropped.
+    /// returns the offset that has to be added to an old position to get its new position.
+    ///
+    /// # Examples
+    /// ```
+    /// // add a column to the left and one to the right of the map
+    /// let offset = map.resize(RectSize::new(map.width() + 2, map.height()), Anchor::Center, Tile::Empty);
+    /// player.position += offset;
+    /// ```
+    pub fn resize(&mut self, new_size: RectSize, anchor: Anchor, fill: T) -> Position
+    where
+        T: Clone,
+    {
+        let offset = anchor.offset(self.size, new_size);
+        self.reframe(new_size, offset, &mut || fill.clone());
+        offset
+    }
+
+    /// trims all borders without any tile that satisfies `is_content`.
+    ///
+    /// returns the position of the kept region inside the old grid,
+    /// `None` if there is no content at all in which case the grid is left untouched.
+    pub fn crop_to_content(&mut self, is_content: &dyn Fn(&T) -> bool) -> Option<Position> {
+        let mut bounds: Option<(Position, Position)> = None;
+        for (position, value) in self.iter_with_position() {
+            if is_content(value) {
+                let (min, max) = bounds.get_or_insert((position, position));
+                *min = Position::new(min.x.min(position.x), min.y.min(position.y));
+                *max = Position::new(max.x.max(position.x), max.y.max(position.y));
+            }
+        }
+
+        let (min, max) = bounds?;
+        let new_size = RectSize::new((max.x - min.x + 1) as usize, (max.y - min.y + 1) as usize);
+        self.reframe(new_size, Position::ZERO - min, &mut || unreachable!("the cropped region is inside the grid"));
+        Some(min)
+    }
+
+    /// moves all values by `offset` into a grid of `new_size`, filling the remaining tiles
+    fn reframe(&mut self, new_size: RectSize, offset: Position, fill: &mut dyn FnMut() -> T) {
+        let old_size = self.size;
+        let mut old_values: Vec<Option<T>> = std::mem::take(&mut self.values).into_vec().into_iter().map(Some).collect();
+
+        let values: Vec<T> = new_size
+            .iter()
+            .map(|position| {
+                let old_position = position - offset;
+                let in_old_bounds = old_position.x >= 0
+                    && old_position.y >= 0
+                    && (old_position.x as usize) < old_size.width
+                    && (old_position.y as usize) < old_size.height;
+                if in_old_bounds {
+                    old_values[to_grid_index(old_position, old_size)].take().unwrap()
+                } else {
+                    fill()
+                }
+            })
+            .collect();
+
+        self.size = new_size;
+        self.values = values.into_boxed_slice();
+    }
+}
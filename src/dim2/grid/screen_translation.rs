@@ -2,25 +2,142 @@
 
 use crate::{dim2::grid::{Position, RectSize}, ScreenView};
 
+/// height of an isometric tile relative to its width, `tan(30°)`
+const ISOMETRIC_HEIGHT_RATIO: f32 = 0.577_350_3;
+
+/// how the tiles of a grid are laid out on the screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Projection {
+    /// axis aligned rectangular tiles stretched to fill the screen view
+    #[default]
+    Orthogonal,
+    /// diamond shaped tiles with a true isometric height, the whole grid forms a large diamond
+    Isometric,
+    /// diamond shaped tiles half as high as they are wide, the common pixel art variant of `Isometric`
+    Dimetric,
+    /// diamond shaped tiles half as high as they are wide, laid out in rows with every odd row shifted
+    /// right by half a tile, so the whole grid forms a rectangle
+    Staggered,
+}
+
+impl Projection {
+    /// height of a tile relative to its width, `None` for `Orthogonal` tiles which are stretched to fit
+    pub const fn tile_height_ratio(&self) -> Option<f32> {
+        match self {
+            Projection::Orthogonal => None,
+            Projection::Isometric => Some(ISOMETRIC_HEIGHT_RATIO),
+            Projection::Dimetric | Projection::Staggered => Some(0.5),
+        }
+    }
+
+    /// width and height of a whole grid of `grid_size` tiles measured in tile widths
+    pub fn grid_extent(&self, grid_size: RectSize) -> (f32, f32) {
+        let (width, height) = (grid_size.width as f32, grid_size.height as f32);
+        let height_ratio = self.tile_height_ratio().unwrap_or(1.);
+        match self {
+            Projection::Orthogonal => (width, height),
+            Projection::Staggered => (width + 0.5, (height + 1.) / 2. * height_ratio),
+            Projection::Isometric | Projection::Dimetric => ((width + height) / 2., (width + height) / 2. * height_ratio),
+        }
+    }
+}
+
 /// converts points inside a grid into their screen coordinates
 #[derive(Clone, Copy)]
 pub struct ScreenTranslation {
     screen_view: ScreenView,
+    /// screen points outside of this area don't hit any tile, the screen view unless clipped, see `clipped_to`
+    hit_area: ScreenView,
     grid_size: RectSize,
     tile_width: f32,
     tile_height: f32,
+    projection: Projection,
+    /// top corner of tile `(0, 0)` for diamond projections, top left corner of the grid otherwise
+    origin_x: f32,
+    origin_y: f32,
 }
 
 impl ScreenTranslation {
     pub fn new(screen_view: ScreenView, grid_size: RectSize) -> ScreenTranslation {
         ScreenTranslation {
             screen_view,
+            hit_area: screen_view,
             grid_size,
             tile_width: screen_view.width / grid_size.width as f32,
             tile_height: screen_view.height / grid_size.height as f32,
+            projection: Projection::Orthogonal,
+            origin_x: screen_view.offset_x,
+            origin_y: screen_view.offset_y,
+        }
+    }
+
+    /// fits the grid into the screen view using `projection`.
+    ///
+    /// diamond shaped tiles keep their aspect ratio, the grid is centered inside of the screen view.
+    ///
+    /// # Examples
+    /// ```
+    /// let translation = ScreenTranslation::with_projection(screen_view, map.size(), Projection::Dimetric);
+    /// for position in translation.iter_draw_order() {
+    ///     let (x, y) = translation.block_center_to_screen_position(position.x, position.y);
+    ///     draw_tile(map[position], x, y);
+    /// }
+    /// let hovered = translation.get_grid_position(mouse_x, mouse_y);
+    /// ```
+    pub fn with_projection(screen_view: ScreenView, grid_size: RectSize, projection: Projection) -> ScreenTranslation {
+        let Some(height_ratio) = projection.tile_height_ratio() else {
+            return Self::new(screen_view, grid_size);
+        };
+
+        let (grid_width, grid_height) = projection.grid_extent(grid_size);
+        let tile_width = (screen_view.width / grid_width).min(screen_view.height / grid_height);
+        let left = screen_view.offset_x + (screen_view.width - grid_width * tile_width) / 2.;
+        let top = screen_view.offset_y + (screen_view.height - grid_height * tile_width) / 2.;
+
+        ScreenTranslation {
+            screen_view,
+            hit_area: screen_view,
+            grid_size,
+            tile_width,
+            tile_height: tile_width * height_ratio,
+            projection,
+            origin_x: match projection {
+                Projection::Staggered => left,
+                _ => left + grid_size.height as f32 * tile_width / 2.,
+            },
+            origin_y: top,
         }
     }
 
+    /// only screen points that are inside of `area` as well hit a tile in `get_grid_position`,
+    /// e.g. when only a part of the grid is visible on the screen
+    pub fn clipped_to(mut self, area: ScreenView) -> ScreenTranslation {
+        let left = self.hit_area.offset_x.max(area.offset_x);
+        let top = self.hit_area.offset_y.max(area.offset_y);
+        let right = (self.hit_area.offset_x + self.hit_area.width).min(area.offset_x + area.width);
+        let bottom = (self.hit_area.offset_y + self.hit_area.height).min(area.offset_y + area.height);
+        self.hit_area = ScreenView {
+            offset_x: left,
+            offset_y: top,
+            width: (right - left).max(0.),
+            height: (bottom - top).max(0.),
+        };
+        self
+    }
+
+    pub const fn projection(&self) -> Projection {
+        self.projection
+    }
+
+    pub const fn tile_width(&self) -> f32 {
+        self.tile_width
+    }
+
+    pub const fn tile_height(&self) -> f32 {
+        self.tile_height
+    }
+
+    /// only meaningful for the orthogonal projection, see `get_grid_position`
     pub fn get_grid_position_x(&self, screen_x: f32) -> Option<i64> {
         if !self.in_screen_bounds_horizontally(screen_x) {
             return None;
@@ -29,6 +146,7 @@ impl ScreenTranslation {
         Some(logic_x as i64)
     }
 
+    /// only meaningful for the orthogonal projection, see `get_grid_position`
     pub fn get_grid_position_y(&self, screen_y: f32) -> Option<i64> {
         if !self.in_screen_bounds_vertically(screen_y) {
             return None;
@@ -38,29 +156,215 @@ impl ScreenTranslation {
     }
 
     fn in_screen_bounds_horizontally(&self, screen_x: f32) -> bool {
-        (self.screen_view.offset_x..self.screen_view.offset_x + self.screen_view.width).contains(&screen_x)
+        (self.hit_area.offset_x..self.hit_area.offset_x + self.hit_area.width).contains(&screen_x)
     }
 
     fn in_screen_bounds_vertically(&self, screen_y: f32) -> bool {
-        (self.screen_view.offset_y..self.screen_view.offset_y + self.screen_view.height).contains(&screen_y)
+        (self.hit_area.offset_y..self.hit_area.offset_y + self.hit_area.height).contains(&screen_y)
     }
 
+    /// tile containing the screen point, diamond shaped tiles are hit-tested against their edges
     pub fn get_grid_position(&self, screen_x: f32, screen_y: f32) -> Option<Position> {
-        let x = self.get_grid_position_x(screen_x)?;
-        let y = self.get_grid_position_y(screen_y)?;
+        if self.projection == Projection::Orthogonal {
+            let x = self.get_grid_position_x(screen_x)?;
+            let y = self.get_grid_position_y(screen_y)?;
+            return Some(Position { x, y });
+        }
 
-        Some(Position { x, y })
+        if !self.in_screen_bounds_horizontally(screen_x) || !self.in_screen_bounds_vertically(screen_y) {
+            return None;
+        }
+        let position = match self.projection {
+            Projection::Staggered => self.staggered_tile_at(screen_x, screen_y)?,
+            _ => self.diamond_tile_at(screen_x, screen_y),
+        };
+        self.contains(position).then_some(position)
     }
 
     pub fn block_center_to_screen_position(&self, x: i64, y: i64) -> (f32, f32) {
-        (self.horizontal_center_to_pixel(x), self.vertical_center_to_pixel(y))
+        match self.projection {
+            Projection::Orthogonal => (self.horizontal_center_to_pixel(x), self.vertical_center_to_pixel(y)),
+            Projection::Staggered => self.staggered_center(Position::new(x, y)),
+            Projection::Isometric | Projection::Dimetric => (
+                self.origin_x + (x - y) as f32 * self.tile_width / 2.,
+                self.origin_y + (x + y + 1) as f32 * self.tile_height / 2.,
+            ),
+        }
     }
 
+    /// only meaningful for the orthogonal projection, see `block_center_to_screen_position`
     pub fn horizontal_center_to_pixel(&self, x: i64) -> f32 {
         self.screen_view.offset_x + (x as f32 * self.tile_width) + (self.tile_width / 2.)
     }
 
+    /// only meaningful for the orthogonal projection, see `block_center_to_screen_position`
     pub fn vertical_center_to_pixel(&self, y: i64) -> f32 {
         self.screen_view.offset_y + (y as f32 * self.tile_height) + (self.tile_height / 2.)
     }
+
+    /// every tile of the grid ordered from back to front, so tiles drawn later may overlap the ones behind them
+    pub fn iter_draw_order(&self) -> impl Iterator<Item = Position> {
+        self.draw_order_in(Position::ZERO, self.grid_size)
+    }
+
+    /// top left position and size of the smallest range of tiles intersecting `viewport`, e.g. the screen,
+    /// grown by `margin` tile widths and heights on every side and clamped to the grid. `None` if no tile is inside of the viewport.
+    ///
+    /// tiles count as visible if their bounding box intersects the viewport. for diamond shaped tiles the range
+    /// also contains tiles in its corners which are outside of the viewport, see `iter_visible_positions`.
+    ///
+    /// # Examples
+    /// ```
+    /// let (offset, size) = translation.visible_range(screen, 1).unwrap();
+    /// let visible_tiles = map.sub_view(offset, size).unwrap();
+    /// ```
+    pub fn visible_range(&self, viewport: ScreenView, margin: usize) -> Option<(Position, RectSize)> {
+        let viewport = self.grow_viewport(viewport, margin as f32);
+        // the bounding box of a diamond reaches half a tile beyond the diamond itself
+        let viewport = match self.projection {
+            Projection::Isometric | Projection::Dimetric => self.grow_viewport(viewport, 0.5),
+            _ => viewport,
+        };
+        let (first, last) = self.covered_tiles(viewport);
+        clamp_tile_range(first, last, 0, self.grid_size)
+    }
+
+    /// positions of all tiles intersecting `viewport` grown by `margin` tiles, ordered from back to front like `iter_draw_order`.
+    /// only the tiles of `visible_range` are visited, so the cost doesn't depend on the size of the grid
+    ///
+    /// # Examples
+    /// ```
+    /// for position in translation.iter_visible_positions(screen, 1) {
+    ///     let (x, y) = translation.block_center_to_screen_position(position.x, position.y);
+    ///     draw_tile(map[position], x, y);
+    /// }
+    /// ```
+    pub fn iter_visible_positions(&self, viewport: ScreenView, margin: usize) -> impl Iterator<Item = Position> + '_ {
+        let (offset, size) = self.visible_range(viewport, margin).unwrap_or((Position::ZERO, RectSize::new(0, 0)));
+        let viewport = self.grow_viewport(viewport, margin as f32);
+        let (left, top) = (viewport.offset_x, viewport.offset_y);
+        let (right, bottom) = (left + viewport.width, top + viewport.height);
+        let (half_width, half_height) = (self.tile_width / 2., self.tile_height / 2.);
+
+        self.draw_order_in(offset, size).filter(move |position| {
+            let (x, y) = self.block_center_to_screen_position(position.x, position.y);
+            x - half_width < right && x + half_width > left && y - half_height < bottom && y + half_height > top
+        })
+    }
+
+    /// viewport grown by `tiles` tile widths and heights on every side
+    fn grow_viewport(&self, viewport: ScreenView, tiles: f32) -> ScreenView {
+        let (grow_x, grow_y) = (tiles * self.tile_width, tiles * self.tile_height);
+        ScreenView {
+            offset_x: viewport.offset_x - grow_x,
+            offset_y: viewport.offset_y - grow_y,
+            width: viewport.width + 2. * grow_x,
+            height: viewport.height + 2. * grow_y,
+        }
+    }
+
+    /// tiles of the range ordered from back to front
+    fn draw_order_in(&self, offset: Position, size: RectSize) -> impl Iterator<Item = Position> {
+        let first = offset;
+        let last = Position::new(offset.x + size.width as i64 - 1, offset.y + size.height as i64 - 1);
+        let is_diamond = matches!(self.projection, Projection::Isometric | Projection::Dimetric);
+        // diamond projections draw one diagonal of equal `x + y` after the other, the others row by row
+        let depths = if size.is_empty() {
+            0..0
+        } else if is_diamond {
+            first.x + first.y..last.x + last.y + 1
+        } else {
+            first.y..last.y + 1
+        };
+
+        depths.flat_map(move |depth| {
+            let columns = if is_diamond {
+                first.x.max(depth - last.y)..last.x.min(depth - first.y) + 1
+            } else {
+                first.x..last.x + 1
+            };
+            columns.map(move |x| if is_diamond { Position::new(x, depth - x) } else { Position::new(x, depth) })
+        })
+    }
+
+    /// first and last tile of the range covering the screen rectangle, not clamped to the grid
+    fn covered_tiles(&self, viewport: ScreenView) -> (Position, Position) {
+        let (left, top) = (viewport.offset_x - self.origin_x, viewport.offset_y - self.origin_y);
+        let (right, bottom) = (left + viewport.width, top + viewport.height);
+        let first_tile = |x: f32, y: f32| Position::new(x.floor() as i64, y.floor() as i64);
+        let last_tile = |x: f32, y: f32| Position::new(x.ceil() as i64 - 1, y.ceil() as i64 - 1);
+
+        match self.projection {
+            Projection::Orthogonal => (
+                first_tile(left / self.tile_width, top / self.tile_height),
+                last_tile(right / self.tile_width, bottom / self.tile_height),
+            ),
+            // rows overlap each other by half of their height and odd rows are shifted right by half a tile
+            Projection::Staggered => {
+                let half_height = self.tile_height / 2.;
+                let first = first_tile((left - self.tile_width / 2.) / self.tile_width, top / half_height);
+                (Position::new(first.x, first.y - 1), last_tile(right / self.tile_width, bottom / half_height))
+            }
+            Projection::Isometric | Projection::Dimetric => {
+                let corners = [(left, top), (right, top), (left, bottom), (right, bottom)].map(|(x, y)| self.diamond_coordinates(x, y));
+                let (min_x, max_x) = corners.iter().fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), &(x, _)| (min.min(x), max.max(x)));
+                let (min_y, max_y) = corners.iter().fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), &(_, y)| (min.min(y), max.max(y)));
+                (first_tile(min_x, min_y), last_tile(max_x, max_y))
+            }
+        }
+    }
+
+    fn contains(&self, position: Position) -> bool {
+        position.x >= 0 && position.y >= 0 && (position.x as usize) < self.grid_size.width && (position.y as usize) < self.grid_size.height
+    }
+
+    fn diamond_tile_at(&self, screen_x: f32, screen_y: f32) -> Position {
+        let (x, y) = self.diamond_coordinates(screen_x - self.origin_x, screen_y - self.origin_y);
+        Position::new(x.floor() as i64, y.floor() as i64)
+    }
+
+    /// fractional grid coordinates of a point relative to the origin,
+    /// diamond tiles form an axis aligned grid once the screen is rotated by 45 degrees
+    fn diamond_coordinates(&self, x: f32, y: f32) -> (f32, f32) {
+        let column = x / (self.tile_width / 2.);
+        let row = y / (self.tile_height / 2.);
+        ((row + column) / 2., (row - column) / 2.)
+    }
+
+    fn staggered_center(&self, position: Position) -> (f32, f32) {
+        let shift = if position.y.rem_euclid(2) == 1 { self.tile_width / 2. } else { 0. };
+        (
+            self.origin_x + (position.x as f32 + 0.5) * self.tile_width + shift,
+            self.origin_y + (position.y + 1) as f32 * self.tile_height / 2.,
+        )
+    }
+
+    /// a screen point is covered by the diamonds of two neighbouring rows at most, the closer one contains it
+    fn staggered_tile_at(&self, screen_x: f32, screen_y: f32) -> Option<Position> {
+        let (half_width, half_height) = (self.tile_width / 2., self.tile_height / 2.);
+        let band = ((screen_y - self.origin_y) / half_height).floor() as i64;
+
+        [band - 1, band]
+            .into_iter()
+            .map(|y| {
+                let shift = if y.rem_euclid(2) == 1 { half_width } else { 0. };
+                let position = Position::new(((screen_x - self.origin_x - shift) / self.tile_width).floor() as i64, y);
+                let (center_x, center_y) = self.staggered_center(position);
+                (position, (screen_x - center_x).abs() / half_width + (screen_y - center_y).abs() / half_height)
+            })
+            .filter(|&(_, distance)| distance <= 1.)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(position, _)| position)
+    }
+}
+
+/// grows the range from `first` to `last` by `margin` tiles and clamps it to the grid, returning its offset and size
+pub(crate) fn clamp_tile_range(first: Position, last: Position, margin: usize, grid_size: RectSize) -> Option<(Position, RectSize)> {
+    let margin = margin as i64;
+    let first = Position::new(first.x.saturating_sub(margin).max(0), first.y.saturating_sub(margin).max(0));
+    let last = Position::new(
+        last.x.saturating_add(margin).min(grid_size.width as i64 - 1),
+        last.y.saturating_add(margin).min(grid_size.height as i64 - 1),
+    );
+    (first.x <= last.x && first.y <= last.y).then(|| (first, RectSize::new((last.x - first.x + 1) as usize, (last.y - first.y + 1) as usize)))
 }
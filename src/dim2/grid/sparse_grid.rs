@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+
+use super::{GridError, GridRead, GridWrite, Position, RectSize};
+
+/// grid that only stores the tiles differing from a default value, suited for huge and mostly empty boards.
+///
+/// without bounds every position is part of the grid, including negative ones.
+///
+/// # Examples
+/// ```
+/// let mut occupancy = SparseGrid::with_bounds(RectSize::new(10_000, 10_000), None);
+/// occupancy.set(unit.position, Some(unit.id)).unwrap();
+/// let path = get_shortest_path(&occupancy, start, end, &|occupant| occupant.is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    default_value: T,
+    bounds: Option<RectSize>,
+    values: HashMap<Position, T>,
+}
+
+impl<T> SparseGrid<T> {
+    /// unbounded grid where every tile starts as `default_value`
+    pub fn new(default_value: T) -> Self {
+        Self {
+            default_value,
+            bounds: None,
+            values: HashMap::new(),
+        }
+    }
+
+    /// grid covering `size` tiles starting at `(0, 0)` where every tile starts as `default_value`
+    pub fn with_bounds(size: RectSize, default_value: T) -> Self {
+        Self {
+            default_value,
+            bounds: Some(size),
+            values: HashMap::new(),
+        }
+    }
+
+    pub const fn bounds(&self) -> Option<RectSize> {
+        self.bounds
+    }
+
+    pub const fn default_value(&self) -> &T {
+        &self.default_value
+    }
+
+    /// amount of tiles that are actually stored
+    pub fn stored_count(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        self.bounds.is_none_or(|size| {
+            position.x >= 0 && position.y >= 0 && (position.x as usize) < size.width && (position.y as usize) < size.height
+        })
+    }
+
+    pub fn get(&self, position: Position) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.get_ref(position).cloned()
+    }
+
+    pub fn get_ref(&self, position: Position) -> Option<&T> {
+        self.contains(position).then(|| self.values.get(&position).unwrap_or(&self.default_value))
+    }
+
+    /// stores a copy of the default value if the tile hasn't been stored yet, see `prune`
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T>
+    where
+        T: Clone,
+    {
+        if !self.contains(position) {
+            return None;
+        }
+        Some(self.values.entry(position).or_insert_with(|| self.default_value.clone()))
+    }
+
+    /// sets the tile at `position`, setting it to the default value frees its storage
+    pub fn set(&mut self, position: Position, value: T) -> Result<(), GridError>
+    where
+        T: PartialEq,
+    {
+        self.check_bounds(position)?;
+        if value == self.default_value {
+            self.values.remove(&position);
+        } else {
+            self.values.insert(position, value);
+        }
+        Ok(())
+    }
+
+    /// resets the tile at `position` to the default value, returning the stored value
+    pub fn remove(&mut self, position: Position) -> Option<T> {
+        self.values.remove(&position)
+    }
+
+    /// frees the storage of all tiles holding the default value, e.g. after changing them via `get_mut`
+    pub fn prune(&mut self)
+    where
+        T: PartialEq,
+    {
+        let default_value = &self.default_value;
+        self.values.retain(|_, value| value != default_value);
+    }
+
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+
+    /// all stored tiles with their position, in no particular order
+    pub fn iter_with_position(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.values.iter().map(|(&position, value)| (position, value))
+    }
+
+    pub fn iter_mut_with_position(&mut self) -> impl Iterator<Item = (Position, &mut T)> {
+        self.values.iter_mut().map(|(&position, value)| (position, value))
+    }
+
+    fn check_bounds(&self, position: Position) -> Result<(), GridError> {
+        match self.bounds {
+            Some(size) if !self.contains(position) => Err(GridError::OutOfBounds { position, size }),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<T> GridRead for SparseGrid<T> {
+    type Tile = T;
+
+    fn bounds(&self) -> Option<RectSize> {
+        self.bounds
+    }
+
+    fn get_ref(&self, position: Position) -> Option<&T> {
+        SparseGrid::get_ref(self, position)
+    }
+}
+
+impl<T: Clone + PartialEq> GridWrite for SparseGrid<T> {
+    fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        SparseGrid::get_mut(self, position)
+    }
+
+    fn set(&mut self, position: Position, value: T) -> Result<(), GridError> {
+        SparseGrid::set(self, position, value)
+    }
+}
@@ -0,0 +1,74 @@
+use super::{Position, RectSize};
+
+/// how positions beyond the edges of a grid are treated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Topology {
+    /// positions outside of the grid don't exist
+    #[default]
+    Bounded,
+    /// the left and right edge are connected
+    WrapHorizontal,
+    /// the top and bottom edge are connected
+    WrapVertical,
+    /// both pairs of opposite edges are connected
+    Torus,
+}
+
+impl Topology {
+    pub const fn wraps_horizontally(&self) -> bool {
+        matches!(self, Topology::WrapHorizontal | Topology::Torus)
+    }
+
+    pub const fn wraps_vertically(&self) -> bool {
+        matches!(self, Topology::WrapVertical | Topology::Torus)
+    }
+
+    /// topology of the grid after swapping its axes, e.g. by a quarter turn
+    pub const fn transposed(&self) -> Topology {
+        match self {
+            Topology::WrapHorizontal => Topology::WrapVertical,
+            Topology::WrapVertical => Topology::WrapHorizontal,
+            Topology::Bounded | Topology::Torus => *self,
+        }
+    }
+
+    /// the position inside a grid of `size` that `position` refers to, `None` if it is outside and doesn't wrap
+    pub fn wrap(&self, position: Position, size: RectSize) -> Option<Position> {
+        Some(Position::new(
+            wrap_axis(position.x, size.width, self.wraps_horizontally())?,
+            wrap_axis(position.y, size.height, self.wraps_vertically())?,
+        ))
+    }
+
+    /// shortest offset from `from` to `to` inside a grid of `size`, crossing the seams where that is shorter
+    pub fn offset(&self, from: Position, to: Position, size: RectSize) -> Position {
+        Position::new(
+            shortest_axis_offset(to.x - from.x, size.width, self.wraps_horizontally()),
+            shortest_axis_offset(to.y - from.y, size.height, self.wraps_vertically()),
+        )
+    }
+}
+
+fn wrap_axis(value: i64, length: usize, wraps: bool) -> Option<i64> {
+    if value >= 0 && (value as usize) < length {
+        Some(value)
+    } else if wraps && length > 0 {
+        Some(value.rem_euclid(length as i64))
+    } else {
+        None
+    }
+}
+
+fn shortest_axis_offset(offset: i64, length: usize, wraps: bool) -> i64 {
+    if !wraps || length == 0 {
+        return offset;
+    }
+
+    let length = length as i64;
+    let offset = offset.rem_euclid(length);
+    if offset > length / 2 {
+        offset - length
+    } else {
+        offset
+    }
+}
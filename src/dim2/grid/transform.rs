@@ -0,0 +1,160 @@
+use super::{to_grid_index, to_grid_position, Direction, Grid, Position, RectSize};
+
+/// clockwise rotation by a multiple of 90 degrees
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Rotation {
+    #[default]
+    None,
+    Clockwise90,
+    Clockwise180,
+    Clockwise270,
+}
+
+impl Rotation {
+    /// rotation that turns something facing `Direction::Up` towards `direction`, `None` for diagonal directions
+    pub const fn from_up_to(direction: Direction) -> Option<Rotation> {
+        match direction {
+            Direction::Up => Some(Rotation::None),
+            Direction::Right => Some(Rotation::Clockwise90),
+            Direction::Down => Some(Rotation::Clockwise180),
+            Direction::Left => Some(Rotation::Clockwise270),
+            _ => None,
+        }
+    }
+
+    /// amount of clockwise quarter turns
+    pub const fn quarter_turns(&self) -> usize {
+        match self {
+            Rotation::None => 0,
+            Rotation::Clockwise90 => 1,
+            Rotation::Clockwise180 => 2,
+            Rotation::Clockwise270 => 3,
+        }
+    }
+
+    pub const fn from_quarter_turns(quarter_turns: usize) -> Rotation {
+        match quarter_turns % 4 {
+            0 => Rotation::None,
+            1 => Rotation::Clockwise90,
+            2 => Rotation::Clockwise180,
+            _ => Rotation::Clockwise270,
+        }
+    }
+
+    /// rotation that undoes this one
+    pub const fn inverse(&self) -> Rotation {
+        Rotation::from_quarter_turns(4 - self.quarter_turns())
+    }
+
+    /// size of a rectangle after being rotated
+    pub const fn rotate_size(&self, size: RectSize) -> RectSize {
+        match self {
+            Rotation::None | Rotation::Clockwise180 => size,
+            Rotation::Clockwise90 | Rotation::Clockwise270 => RectSize::new(size.height, size.width),
+        }
+    }
+
+    /// where `position` inside a rectangle of `size` ends up after rotating the rectangle
+    pub const fn rotate_position(&self, position: Position, size: RectSize) -> Position {
+        let (width, height) = (size.width as i64, size.height as i64);
+        match self {
+            Rotation::None => position,
+            Rotation::Clockwise90 => Position::new(height - 1 - position.y, position.x),
+            Rotation::Clockwise180 => Position::new(width - 1 - position.x, height - 1 - position.y),
+            Rotation::Clockwise270 => Position::new(position.y, width - 1 - position.x),
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// rotates the grid clockwise, the width and height are swapped for quarter turns together with the wrapping edges
+    pub fn rotate(&mut self, rotation: Rotation) {
+        match rotation {
+            Rotation::None => {}
+            Rotation::Clockwise180 => self.values.reverse(),
+            Rotation::Clockwise90 | Rotation::Clockwise270 => {
+                let size = self.size;
+                self.permute(rotation.rotate_size(size), |position| rotation.rotate_position(position, size));
+                self.topology = self.topology.transposed();
+            }
+        }
+    }
+
+    /// mirrors the grid along its vertical axis
+    pub fn flip_horizontally(&mut self) {
+        if self.size.width > 0 {
+            for row in self.values.chunks_mut(self.size.width) {
+                row.reverse();
+            }
+        }
+    }
+
+    /// mirrors the grid along its horizontal axis
+    pub fn flip_vertically(&mut self) {
+        let (width, height) = (self.size.width, self.size.height);
+        for y in 0..height / 2 {
+            let (upper, lower) = self.values.split_at_mut((height - 1 - y) * width);
+            upper[y * width..(y + 1) * width].swap_with_slice(&mut lower[..width]);
+        }
+    }
+
+    /// mirrors the grid along its main diagonal, swapping width and height together with the wrapping edges
+    pub fn transpose(&mut self) {
+        let size = self.size;
+        self.permute(RectSize::new(size.height, size.width), |position| Position::new(position.y, position.x));
+        self.topology = self.topology.transposed();
+    }
+
+    pub fn rotated(&self, rotation: Rotation) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let mut grid = self.clone();
+        grid.rotate(rotation);
+        grid
+    }
+
+    pub fn flipped_horizontally(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let mut grid = self.clone();
+        grid.flip_horizontally();
+        grid
+    }
+
+    pub fn flipped_vertically(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let mut grid = self.clone();
+        grid.flip_vertically();
+        grid
+    }
+
+    pub fn transposed(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let mut grid = self.clone();
+        grid.transpose();
+        grid
+    }
+
+    /// moves every value to `target(position)` in a grid of `new_size` without cloning any value
+    fn permute(&mut self, new_size: RectSize, target: impl Fn(Position) -> Position) {
+        let mut destinations: Vec<usize> = (0..self.values.len())
+            .map(|index| to_grid_index(target(to_grid_position(index, self.size)), new_size))
+            .collect();
+
+        for index in 0..destinations.len() {
+            while destinations[index] != index {
+                let destination = destinations[index];
+                self.values.swap(index, destination);
+                destinations.swap(index, destination);
+            }
+        }
+
+        self.size = new_size;
+    }
+}
@@ -0,0 +1,100 @@
+use super::{Hex, OffsetLayout};
+use crate::dim2::grid::{Grid, GridError, Position, RectSize, Topology};
+
+/// rectangular hex board whose tiles are stored in a `Grid` using an offset layout.
+///
+/// the board never wraps, offset layouts don't line up across the seams of a wrapping grid.
+///
+/// # Examples
+/// ```
+/// let mut board = HexGrid::new(Grid::new(12, 8, Terrain::Plains), OffsetLayout::OddRows);
+/// board.set(Hex::new(3, 2), Terrain::Forest).unwrap();
+/// let surrounding: Vec<_> = board.neighbors(unit.hex).filter_map(|hex| board.get_ref(hex)).collect();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HexGrid<T> {
+    grid: Grid<T>,
+    layout: OffsetLayout,
+}
+
+impl<T> HexGrid<T> {
+    /// the topology of `grid` is reset to `Topology::Bounded`
+    pub fn new(mut grid: Grid<T>, layout: OffsetLayout) -> Self {
+        grid.set_topology(Topology::Bounded);
+        Self { grid, layout }
+    }
+
+    pub const fn grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    pub fn grid_mut(&mut self) -> &mut Grid<T> {
+        &mut self.grid
+    }
+
+    pub fn into_grid(self) -> Grid<T> {
+        self.grid
+    }
+
+    pub const fn layout(&self) -> OffsetLayout {
+        self.layout
+    }
+
+    pub const fn size(&self) -> RectSize {
+        self.grid.size()
+    }
+
+    /// grid position storing `hex`
+    pub const fn to_position(&self, hex: Hex) -> Position {
+        self.layout.to_position(hex)
+    }
+
+    /// hex stored at the grid position `position`
+    pub const fn to_hex(&self, position: Position) -> Hex {
+        self.layout.to_hex(position)
+    }
+
+    pub fn contains(&self, hex: Hex) -> bool {
+        self.checked_position(hex).is_some()
+    }
+
+    pub fn get(&self, hex: Hex) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.get_ref(hex).cloned()
+    }
+
+    pub fn get_ref(&self, hex: Hex) -> Option<&T> {
+        self.grid.get_ref(self.checked_position(hex)?)
+    }
+
+    pub fn get_mut(&mut self, hex: Hex) -> Option<&mut T> {
+        self.grid.get_mut(self.checked_position(hex)?)
+    }
+
+    pub fn set(&mut self, hex: Hex, value: T) -> Result<(), GridError> {
+        let position = self.to_position(hex);
+        let size = self.size();
+        self.checked_position(hex).ok_or(GridError::OutOfBounds { position, size })?;
+        self.grid.set(position, value)
+    }
+
+    /// neighbours of `hex` that are part of the board
+    pub fn neighbors(&self, hex: Hex) -> impl Iterator<Item = Hex> + '_ {
+        hex.neighbors().into_iter().filter(|&neighbor| self.contains(neighbor))
+    }
+
+    /// every tile with its hex in row major order of the grid
+    pub fn iter_with_hex(&self) -> impl Iterator<Item = (Hex, &T)> {
+        self.grid.iter_with_position().map(|(position, value)| (self.to_hex(position), value))
+    }
+
+    /// grid position storing `hex` if it is inside of the grid, checked explicitly since the grid
+    /// may have been given a wrapping topology through `grid_mut`
+    fn checked_position(&self, hex: Hex) -> Option<Position> {
+        let position = self.to_position(hex);
+        let size = self.size();
+        ((0..size.width as i64).contains(&position.x) && (0..size.height as i64).contains(&position.y)).then_some(position)
+    }
+}
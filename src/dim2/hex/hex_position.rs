@@ -0,0 +1,163 @@
+use std::ops::{Add, AddAssign, Mul, Sub, SubAssign};
+
+/// position on a hexagonal grid in axial coordinates.
+///
+/// the implicit third cube coordinate is `s = -q - r`, see `s`.
+/// directions don't depend on the orientation of the hexagons, `HexScreenTranslation` decides how they are drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Hex {
+    pub q: i64,
+    pub r: i64,
+}
+
+impl Hex {
+    pub const ZERO: Hex = Hex { q: 0, r: 0 };
+
+    /// offsets to the six neighbours, each one turning further around the center
+    pub const DIRECTIONS: [Hex; 6] = [
+        Hex::new(1, 0),
+        Hex::new(1, -1),
+        Hex::new(0, -1),
+        Hex::new(-1, 0),
+        Hex::new(-1, 1),
+        Hex::new(0, 1),
+    ];
+
+    pub const fn new(q: i64, r: i64) -> Self {
+        Hex { q, r }
+    }
+
+    /// # Panics
+    /// if the cube coordinates don't sum up to zero
+    pub const fn from_cube(q: i64, r: i64, s: i64) -> Self {
+        assert!(q + r + s == 0, "cube coordinates have to sum up to zero");
+        Hex { q, r }
+    }
+
+    /// third cube coordinate
+    pub const fn s(&self) -> i64 {
+        -self.q - self.r
+    }
+
+    pub const fn cube(&self) -> (i64, i64, i64) {
+        (self.q, self.r, self.s())
+    }
+
+    /// nearest hex to fractional axial coordinates
+    pub fn round(q: f64, r: f64) -> Hex {
+        let s = -q - r;
+        let (mut rounded_q, mut rounded_r, rounded_s) = (q.round(), r.round(), s.round());
+        let (q_diff, r_diff, s_diff) = ((rounded_q - q).abs(), (rounded_r - r).abs(), (rounded_s - s).abs());
+
+        if q_diff > r_diff && q_diff > s_diff {
+            rounded_q = -rounded_r - rounded_s;
+        } else if r_diff > s_diff {
+            rounded_r = -rounded_q - rounded_s;
+        }
+        Hex::new(rounded_q as i64, rounded_r as i64)
+    }
+
+    /// the neighbour in `Hex::DIRECTIONS[direction % 6]`
+    pub const fn neighbor(&self, direction: usize) -> Hex {
+        let offset = Hex::DIRECTIONS[direction % 6];
+        Hex::new(self.q + offset.q, self.r + offset.r)
+    }
+
+    pub fn neighbors(&self) -> [Hex; 6] {
+        Hex::DIRECTIONS.map(|offset| *self + offset)
+    }
+
+    /// amount of steps between both hexes
+    pub const fn distance(&self, other: Hex) -> i64 {
+        let (q, r, s) = (self.q - other.q, self.r - other.r, self.s() - other.s());
+        (q.abs() + r.abs() + s.abs()) / 2
+    }
+
+    /// all hexes at exactly `radius` steps, walking around the center
+    pub fn ring(&self, radius: usize) -> Vec<Hex> {
+        if radius == 0 {
+            return vec![*self];
+        }
+
+        let mut result = Vec::with_capacity(6 * radius);
+        let mut hex = *self + Hex::DIRECTIONS[4] * radius as i64;
+        for direction in 0..6 {
+            for _ in 0..radius {
+                result.push(hex);
+                hex = hex.neighbor(direction);
+            }
+        }
+        result
+    }
+
+    /// all hexes within `radius` steps, starting at the center and continuing ring by ring
+    pub fn spiral(&self, radius: usize) -> Vec<Hex> {
+        (0..=radius).flat_map(|ring_radius| self.ring(ring_radius)).collect()
+    }
+
+    /// hexes on the straight line to `other`, including both ends
+    pub fn line_to(&self, other: Hex) -> Vec<Hex> {
+        let steps = self.distance(other);
+        if steps == 0 {
+            return vec![*self];
+        }
+
+        // nudging the line avoids ties when it runs exactly along the edge between two hexes
+        let (from_q, from_r) = (self.q as f64 + 1e-6, self.r as f64 + 2e-6);
+        let (to_q, to_r) = (other.q as f64 + 1e-6, other.r as f64 + 2e-6);
+        (0..=steps)
+            .map(|step| {
+                let t = step as f64 / steps as f64;
+                Hex::round(from_q + (to_q - from_q) * t, from_r + (to_r - from_r) * t)
+            })
+            .collect()
+    }
+}
+
+impl From<(i64, i64)> for Hex {
+    fn from((q, r): (i64, i64)) -> Self {
+        Hex::new(q, r)
+    }
+}
+
+impl From<(i32, i32)> for Hex {
+    fn from((q, r): (i32, i32)) -> Self {
+        Hex::new(q as i64, r as i64)
+    }
+}
+
+impl Add for Hex {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Hex::new(self.q + other.q, self.r + other.r)
+    }
+}
+
+impl Sub for Hex {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Hex::new(self.q - other.q, self.r - other.r)
+    }
+}
+
+impl AddAssign for Hex {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Hex {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<i64> for Hex {
+    type Output = Self;
+
+    fn mul(self, factor: i64) -> Self {
+        Hex::new(self.q * factor, self.r * factor)
+    }
+}
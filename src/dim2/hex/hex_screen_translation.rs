@@ -0,0 +1,106 @@
+use super::Hex;
+use crate::ScreenView;
+
+const SQRT_3: f32 = 1.732_050_8;
+
+/// which way the hexagons are drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HexOrientation {
+    /// a corner points up, hexes of the same row share vertical edges
+    #[default]
+    PointyTop,
+    /// an edge is on top, hexes of the same column share horizontal edges
+    FlatTop,
+}
+
+/// converts hexes into their screen coordinates and back, the hex counterpart of `ScreenTranslation`.
+///
+/// hex `(0, 0)` touches the top left corner of the screen view, so a board stored with the matching
+/// `OffsetLayout` (rows for pointy top, columns for flat top) extends to the right and down.
+///
+/// # Examples
+/// ```
+/// let translation = HexScreenTranslation::new(screen_view, HexOrientation::PointyTop, 32.);
+/// if let Some(hex) = translation.get_hex(mouse_x, mouse_y) {
+///     select(hex);
+/// }
+/// let (x, y) = translation.hex_center_to_screen_position(unit.hex);
+/// ```
+#[derive(Clone, Copy)]
+pub struct HexScreenTranslation {
+    screen_view: ScreenView,
+    orientation: HexOrientation,
+    hex_size: f32,
+}
+
+impl HexScreenTranslation {
+    /// `hex_size` is the distance from the center of a hex to its corners
+    pub fn new(screen_view: ScreenView, orientation: HexOrientation, hex_size: f32) -> HexScreenTranslation {
+        HexScreenTranslation {
+            screen_view,
+            orientation,
+            hex_size,
+        }
+    }
+
+    pub const fn orientation(&self) -> HexOrientation {
+        self.orientation
+    }
+
+    pub const fn hex_size(&self) -> f32 {
+        self.hex_size
+    }
+
+    /// width and height of a single hex
+    pub fn hex_extent(&self) -> (f32, f32) {
+        match self.orientation {
+            HexOrientation::PointyTop => (SQRT_3 * self.hex_size, 2. * self.hex_size),
+            HexOrientation::FlatTop => (2. * self.hex_size, SQRT_3 * self.hex_size),
+        }
+    }
+
+    /// hex containing the screen point, `None` if the point is outside of the screen view
+    pub fn get_hex(&self, screen_x: f32, screen_y: f32) -> Option<Hex> {
+        let view = self.screen_view;
+        if !(view.offset_x..view.offset_x + view.width).contains(&screen_x) || !(view.offset_y..view.offset_y + view.height).contains(&screen_y) {
+            return None;
+        }
+
+        let (origin_x, origin_y) = self.origin();
+        let (x, y) = ((screen_x - origin_x) / self.hex_size, (screen_y - origin_y) / self.hex_size);
+        let (q, r) = match self.orientation {
+            HexOrientation::PointyTop => (SQRT_3 / 3. * x - y / 3., 2. / 3. * y),
+            HexOrientation::FlatTop => (2. / 3. * x, -x / 3. + SQRT_3 / 3. * y),
+        };
+        Some(Hex::round(q as f64, r as f64))
+    }
+
+    pub fn hex_center_to_screen_position(&self, hex: Hex) -> (f32, f32) {
+        let (q, r) = (hex.q as f32, hex.r as f32);
+        let (x, y) = match self.orientation {
+            HexOrientation::PointyTop => (SQRT_3 * q + SQRT_3 / 2. * r, 1.5 * r),
+            HexOrientation::FlatTop => (1.5 * q, SQRT_3 / 2. * q + SQRT_3 * r),
+        };
+        let (origin_x, origin_y) = self.origin();
+        (origin_x + x * self.hex_size, origin_y + y * self.hex_size)
+    }
+
+    /// screen positions of the six corners of `hex`, e.g. to draw its outline
+    pub fn hex_corners(&self, hex: Hex) -> [(f32, f32); 6] {
+        let (center_x, center_y) = self.hex_center_to_screen_position(hex);
+        let start_angle: f32 = match self.orientation {
+            HexOrientation::PointyTop => 30.,
+            HexOrientation::FlatTop => 0.,
+        };
+        std::array::from_fn(|corner| {
+            let angle = (start_angle + 60. * corner as f32).to_radians();
+            (center_x + self.hex_size * angle.cos(), center_y + self.hex_size * angle.sin())
+        })
+    }
+
+    /// screen position of the center of hex `(0, 0)`
+    fn origin(&self) -> (f32, f32) {
+        let (width, height) = self.hex_extent();
+        (self.screen_view.offset_x + width / 2., self.screen_view.offset_y + height / 2.)
+    }
+}
@@ -0,0 +1,10 @@
+pub mod hex_grid;
+pub use hex_grid::HexGrid;
+pub mod hex_position;
+pub use hex_position::Hex;
+pub mod hex_screen_translation;
+pub use hex_screen_translation::{HexOrientation, HexScreenTranslation};
+pub mod offset_layout;
+pub use offset_layout::OffsetLayout;
+pub mod pathing;
+pub use pathing::*;
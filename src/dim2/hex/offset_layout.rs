@@ -0,0 +1,40 @@
+use super::Hex;
+use crate::dim2::grid::Position;
+
+/// how hexes are laid out in a rectangular `Grid`, every other row or column is shifted by half a hex.
+///
+/// row layouts are meant for pointy top hexagons, column layouts for flat top hexagons.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum OffsetLayout {
+    /// odd rows are shifted right
+    #[default]
+    OddRows,
+    /// even rows are shifted right
+    EvenRows,
+    /// odd columns are shifted down
+    OddColumns,
+    /// even columns are shifted down
+    EvenColumns,
+}
+
+impl OffsetLayout {
+    /// grid position storing `hex`
+    pub const fn to_position(&self, hex: Hex) -> Position {
+        match self {
+            OffsetLayout::OddRows => Position::new(hex.q + (hex.r - (hex.r & 1)) / 2, hex.r),
+            OffsetLayout::EvenRows => Position::new(hex.q + (hex.r + (hex.r & 1)) / 2, hex.r),
+            OffsetLayout::OddColumns => Position::new(hex.q, hex.r + (hex.q - (hex.q & 1)) / 2),
+            OffsetLayout::EvenColumns => Position::new(hex.q, hex.r + (hex.q + (hex.q & 1)) / 2),
+        }
+    }
+
+    /// hex stored at the grid position `position`
+    pub const fn to_hex(&self, position: Position) -> Hex {
+        match self {
+            OffsetLayout::OddRows => Hex::new(position.x - (position.y - (position.y & 1)) / 2, position.y),
+            OffsetLayout::EvenRows => Hex::new(position.x - (position.y + (position.y & 1)) / 2, position.y),
+            OffsetLayout::OddColumns => Hex::new(position.x, position.y - (position.x - (position.x & 1)) / 2),
+            OffsetLayout::EvenColumns => Hex::new(position.x, position.y - (position.x + (position.x & 1)) / 2),
+        }
+    }
+}
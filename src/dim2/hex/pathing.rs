@@ -0,0 +1,45 @@
+use pathfinding::prelude::astar;
+
+use super::{Hex, HexGrid};
+
+pub fn hex_path_exists<T>(grid: &HexGrid<T>, start: Hex, end: Hex, is_pathable_tile: &dyn Fn(&T) -> bool) -> bool {
+    get_shortest_hex_path(grid, start, end, is_pathable_tile).is_some()
+}
+
+/// finds a path with the least steps from `start` to `end`, see `get_shortest_weighted_hex_path`
+pub fn get_shortest_hex_path<T>(grid: &HexGrid<T>, start: Hex, end: Hex, is_pathable_tile: &dyn Fn(&T) -> bool) -> Option<(Vec<Hex>, i64)> {
+    let tile_cost = |tile: &T| is_pathable_tile(tile).then_some(1);
+    get_shortest_weighted_hex_path(grid, start, end, &tile_cost)
+}
+
+/// finds the cheapest path from `start` to `end`, the hex counterpart of `get_shortest_weighted_path`.
+///
+/// `tile_cost` is described in the grid `pathing` module documentation.
+/// costs are expected to be at least 1 for the heuristic to stay admissible.
+///
+/// # Examples
+/// ```
+/// let tile_cost = |terrain: &Terrain| match terrain {
+///     Terrain::Plains => Some(1),
+///     Terrain::Forest => Some(2),
+///     Terrain::Mountain => None,
+/// };
+/// let (path, cost) = get_shortest_weighted_hex_path(&board, unit.hex, target, &tile_cost).unwrap();
+/// ```
+pub fn get_shortest_weighted_hex_path<T>(
+    grid: &HexGrid<T>,
+    start: Hex,
+    end: Hex,
+    tile_cost: &dyn Fn(&T) -> Option<i64>,
+) -> Option<(Vec<Hex>, i64)> {
+    astar(
+        &start,
+        |&node| {
+            grid.neighbors(node)
+                .filter_map(|neighbor| Some((neighbor, tile_cost(grid.get_ref(neighbor)?)?)))
+                .collect::<Vec<_>>()
+        },
+        |node| node.distance(end),
+        |node| *node == end,
+    )
+}
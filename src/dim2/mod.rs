@@ -1,4 +1,6 @@
 pub mod grid;
+pub mod hex;
+pub mod camera;
 pub mod collider;
 pub mod vec;
 pub mod movement;
@@ -0,0 +1,108 @@
+use xs_games_rs::dim2::grid::{
+    pathing::get_shortest_path,
+    patterns::{cross_pattern, GridPattern, PatternPositions},
+    BitGrid, Grid, GridError, Position, RectSize,
+};
+
+/// deterministic pseudo random mask spanning several words per row
+fn noise_grid(width: usize, height: usize, seed: u64) -> Grid<bool> {
+    let mut state = seed;
+    let values: Vec<bool> = (0..width * height)
+        .map(|_| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            state >> 63 == 1
+        })
+        .collect();
+    Grid::with_preset_values(width, height, values.into_boxed_slice())
+}
+
+#[test]
+fn get_and_set_across_words() {
+    let mut grid = BitGrid::new(70, 3, false);
+    grid.set(Position::new(63, 1), true).unwrap();
+    grid.set(Position::new(64, 1), true).unwrap();
+    grid.set(Position::new(69, 2), true).unwrap();
+    assert_eq!(grid.get(Position::new(64, 1)), Some(true));
+    assert_eq!(grid.get(Position::new(65, 1)), Some(false));
+    assert_eq!(grid.get(Position::new(70, 0)), None);
+    assert_eq!(
+        grid.set(Position::new(0, 3), true),
+        Err(GridError::OutOfBounds {
+            position: Position::new(0, 3),
+            size: RectSize::new(70, 3)
+        })
+    );
+    assert_eq!(grid.count_ones(), 3);
+    assert_eq!(
+        grid.iter_set_positions().collect::<Vec<_>>(),
+        [Position::new(63, 1), Position::new(64, 1), Position::new(69, 2)]
+    );
+
+    let filled = BitGrid::new(70, 3, true);
+    assert_eq!(filled.count_ones(), 210);
+    assert_eq!((!&filled).count_ones(), 0);
+    assert!(!BitGrid::new(0, 5, true).any());
+}
+
+#[test]
+fn set_operations_match_grid() {
+    let a = noise_grid(100, 7, 1);
+    let b = noise_grid(100, 7, 2);
+    let (bits_a, bits_b) = (BitGrid::from(&a), BitGrid::from(&b));
+    assert_eq!(bits_a.to_grid(), a);
+
+    let expected = |operation: fn(bool, bool) -> bool| {
+        let values: Vec<_> = a.iter().zip(b.iter()).map(|(&a, &b)| operation(a, b)).collect();
+        Grid::with_preset_values(100, 7, values.into_boxed_slice())
+    };
+    assert_eq!((&bits_a & &bits_b).to_grid(), expected(|a, b| a && b));
+    assert_eq!((&bits_a | &bits_b).to_grid(), expected(|a, b| a || b));
+    assert_eq!((&bits_a ^ &bits_b).to_grid(), expected(|a, b| a != b));
+    assert_eq!((!&bits_a).to_grid(), expected(|a, _| !a));
+    assert_eq!((!&bits_a).count_ones(), 700 - bits_a.count_ones());
+
+    let mut combined = bits_a.clone();
+    combined |= &bits_b;
+    combined &= &bits_a;
+    assert_eq!(combined, bits_a);
+}
+
+#[test]
+fn shifted_matches_grid() {
+    let grid = noise_grid(130, 5, 3);
+    let bits = BitGrid::from(&grid);
+
+    for offset in [(0, 0), (1, 0), (-1, 2), (64, -1), (-65, 0), (127, 1), (130, 0), (3, -5)].map(Position::from) {
+        let shifted = bits.shifted(offset);
+        for position in grid.size().iter() {
+            let expected = grid.get(position - offset).unwrap_or(false);
+            assert_eq!(shifted.get(position), Some(expected), "{position:?} shifted by {offset:?}");
+        }
+    }
+}
+
+#[test]
+fn shifted_by_extreme_offsets() {
+    let bits = BitGrid::new(70, 3, true);
+    for offset in [(i64::MIN, 0), (i64::MAX, 0), (0, i64::MIN), (0, i64::MAX), (i64::MIN, i64::MAX), (1, i64::MIN)].map(Position::from) {
+        assert!(!bits.shifted(offset).any(), "shifted by {offset:?}");
+    }
+    assert_eq!(bits.shifted(Position::new(-69, 2)).count_ones(), 1);
+}
+
+#[test]
+fn patterns_and_pathing() {
+    let mut walls = BitGrid::new(5, 5, false);
+    walls.set_pattern(Position::new(2, 2), cross_pattern(1).as_ref(), true);
+    walls.set_pattern(Position::new(0, 0), cross_pattern(1).as_ref(), false);
+    assert_eq!(walls.count_ones(), 5);
+
+    let pattern = GridPattern {
+        mapping: walls.to_grid(),
+        center: Position::new(2, 2),
+    };
+    assert_eq!(pattern.get_pattern_positions(Position::new(2, 2), true).len(), 5);
+
+    let (_, cost) = get_shortest_path(&walls, Position::new(0, 2), Position::new(4, 2), &|&wall| !wall).unwrap();
+    assert_eq!(cost, 8);
+}
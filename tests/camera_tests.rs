@@ -0,0 +1,195 @@
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+use xs_games_rs::{
+    dim2::{
+        camera::Camera2D,
+        collider::Rectangle,
+        grid::{Position, Projection, RectSize},
+    },
+    ScreenView,
+};
+
+fn create_test_camera() -> Camera2D {
+    Camera2D::new(ScreenView {
+        offset_x: 100.,
+        offset_y: 50.,
+        width: 400.,
+        height: 300.,
+    })
+}
+
+fn assert_close((a_x, a_y): (f32, f32), (b_x, b_y): (f32, f32)) {
+    assert!((a_x - b_x).abs() < 1e-3 && (a_y - b_y).abs() < 1e-3, "({a_x}, {a_y}) != ({b_x}, {b_y})");
+}
+
+#[test]
+fn world_screen_round_trip() {
+    let mut camera = create_test_camera();
+    assert_eq!(camera.world_to_screen(0., 0.), (300., 200.));
+
+    camera.set_center(10., 20.);
+    camera.set_zoom(2.);
+    assert_eq!(camera.world_to_screen(15., 20.), (310., 200.));
+
+    camera.set_rotation(FRAC_PI_2);
+    // a world point right of the center appears above it
+    assert_close(camera.world_to_screen(15., 20.), (300., 190.));
+    for point in [(0., 0.), (-31.5, 7.25), (100., -3.)] {
+        let (screen_x, screen_y) = camera.world_to_screen(point.0, point.1);
+        assert_close(camera.screen_to_world(screen_x, screen_y), point);
+    }
+
+    camera.pan(0., -10.);
+    assert_close(camera.center(), (15., 20.));
+}
+
+#[test]
+fn zoom_at_keeps_point_in_place() {
+    let mut camera = create_test_camera();
+    let before = camera.screen_to_world(150., 80.);
+    camera.zoom_at(4., 150., 80.);
+    assert_eq!(camera.zoom(), 4.);
+    assert_close(camera.screen_to_world(150., 80.), before);
+}
+
+#[test]
+fn clamps_to_world_bounds() {
+    let mut camera = create_test_camera();
+    camera.set_world_bounds(Some(Rectangle {
+        left: 0.,
+        top: 0.,
+        right: 1000.,
+        bottom: 200.,
+    }));
+    // the bounds are less high than the screen view, so the camera is centered vertically
+    assert_eq!(camera.center(), (200., 100.));
+
+    camera.set_center(990., -50.);
+    assert_eq!(camera.center(), (800., 100.));
+    let area = camera.visible_world_area();
+    assert_eq!((area.left, area.right), (600., 1000.));
+
+    camera.set_zoom(2.);
+    camera.set_center(0., 0.);
+    assert_eq!(camera.center(), (100., 75.));
+
+    camera.set_rotation(FRAC_PI_2);
+    camera.set_center(0., 0.);
+    assert_close(camera.center(), (75., 100.));
+}
+
+#[test]
+fn follow_converges() {
+    let mut camera = create_test_camera();
+    camera.lerp_towards(100., -50., 0.5);
+    assert_eq!(camera.center(), (50., -25.));
+
+    let mut previous_distance = f32::INFINITY;
+    for _ in 0..120 {
+        camera.follow(100., -50., 5., 1. / 60.);
+        let (x, y) = camera.center();
+        let distance = (100. - x).hypot(-50. - y);
+        assert!(distance < previous_distance);
+        previous_distance = distance;
+    }
+    assert!(previous_distance < 0.01);
+
+    // the same time split into fewer frames ends at the same place
+    let mut coarse = create_test_camera();
+    coarse.lerp_towards(100., -50., 0.5);
+    for _ in 0..4 {
+        coarse.follow(100., -50., 5., 0.5);
+    }
+    assert_close(coarse.center(), camera.center());
+}
+
+#[test]
+fn screen_shake_fades_out() {
+    let mut camera = create_test_camera();
+    camera.shake(10., 1.);
+    camera.update(0.1);
+    let (x, y) = camera.shake_offset();
+    assert!(x != 0. || y != 0.);
+    assert!(x.abs() <= 9. && y.abs() <= 9.);
+    assert_close(camera.world_to_screen(0., 0.), (300. + x, 200. + y));
+
+    camera.update(0.8);
+    let (x, y) = camera.shake_offset();
+    assert!(x.abs() <= 1. && y.abs() <= 1.);
+
+    camera.update(0.2);
+    assert_eq!(camera.shake_offset(), (0., 0.));
+}
+
+#[test]
+fn produces_screen_translation() {
+    let mut camera = create_test_camera();
+    camera.set_center(80., 40.);
+    camera.set_zoom(2.);
+
+    let grid_size = RectSize::new(10, 8);
+    let translation = camera.screen_translation(grid_size, Projection::Orthogonal, 16.).unwrap();
+    assert_eq!((translation.tile_width(), translation.tile_height()), (32., 32.));
+    assert_eq!(translation.block_center_to_screen_position(0, 0), camera.world_to_screen(8., 8.));
+    let (screen_x, screen_y) = camera.world_to_screen(8. + 16. * 3., 8. + 16. * 5.);
+    assert_eq!(translation.get_grid_position(screen_x, screen_y), Some(Position::new(3, 5)));
+
+    let dimetric = camera.screen_translation(grid_size, Projection::Dimetric, 16.).unwrap();
+    assert_eq!((dimetric.tile_width(), dimetric.tile_height()), (32., 16.));
+    // the top corner of tile (0, 0) lies above the world origin shifted by the height of the grid
+    assert_close(dimetric.block_center_to_screen_position(0, 0), camera.world_to_screen(64., 4.));
+
+    // the grid reaches beyond the screen view, points outside of it don't hit a tile
+    let (bottom_x, bottom_y) = camera.world_to_screen(8. + 16. * 3., 8. + 16. * 7.);
+    assert!(bottom_y > 350.);
+    assert_eq!(translation.get_grid_position(bottom_x, bottom_y), None);
+    assert_eq!(translation.get_grid_position(bottom_x, 349.), Some(Position::new(3, 7)));
+
+    camera.set_rotation(0.3);
+    assert!(camera.screen_translation(grid_size, Projection::Orthogonal, 16.).is_none());
+    camera.set_rotation(TAU);
+    assert!(camera.screen_translation(grid_size, Projection::Orthogonal, 16.).is_some());
+}
+
+#[test]
+fn screen_translation_after_accumulated_rotation() {
+    let mut camera = create_test_camera();
+    let grid_size = RectSize::new(10, 8);
+
+    // a full turn spread over many frames doesn't end up at exactly TAU
+    let frame_delta = TAU / 60.;
+    for _ in 0..60 {
+        camera.set_rotation(camera.rotation() + frame_delta);
+    }
+    assert_ne!(camera.rotation(), TAU);
+    assert!(camera.screen_translation(grid_size, Projection::Orthogonal, 16.).is_some());
+
+    camera.set_rotation(4. * FRAC_PI_2);
+    assert!(camera.screen_translation(grid_size, Projection::Orthogonal, 16.).is_some());
+    camera.set_rotation(-3. * TAU + 1e-6);
+    assert!(camera.screen_translation(grid_size, Projection::Orthogonal, 16.).is_some());
+    camera.set_rotation(TAU - 0.01);
+    assert!(camera.screen_translation(grid_size, Projection::Orthogonal, 16.).is_none());
+}
+
+#[test]
+fn visible_tile_range() {
+    let mut camera = create_test_camera();
+    camera.set_center(200., 150.);
+    let grid_size = RectSize::new(1000, 1000);
+    // the screen view shows world (0, 0) to (400, 300), tiles are 16 units wide
+    assert_eq!(camera.visible_tile_range(grid_size, 16., 0), Some((Position::new(0, 0), RectSize::new(25, 19))));
+    assert_eq!(camera.visible_tile_range(grid_size, 16., 1), Some((Position::new(0, 0), RectSize::new(26, 20))));
+
+    camera.set_center(1000., 1000.);
+    camera.set_zoom(2.);
+    assert_eq!(camera.visible_tile_range(grid_size, 16., 0), Some((Position::new(56, 57), RectSize::new(13, 11))));
+
+    // the bounding box of the rotated view is larger
+    camera.set_rotation(FRAC_PI_2);
+    let (_, rotated) = camera.visible_tile_range(grid_size, 16., 0).unwrap();
+    assert_eq!((rotated.width, rotated.height), (11, 13));
+
+    camera.set_center(-500., 0.);
+    assert_eq!(camera.visible_tile_range(grid_size, 16., 0), None);
+}
@@ -0,0 +1,104 @@
+use std::{cell::RefCell, rc::Rc};
+
+use xs_games_rs::dim2::grid::{patterns::adjacent_pattern, ChunkedGrid, Grid, GridError, Position, RectSize};
+
+#[test]
+fn negative_positions() {
+    let mut grid = ChunkedGrid::new(RectSize::new(4, 3), 0);
+    assert_eq!(grid.chunk_position(Position::new(-1, -1)), Position::new(-1, -1));
+    assert_eq!(grid.local_position(Position::new(-1, -1)), Position::new(3, 2));
+    assert_eq!(grid.chunk_position(Position::new(4, 2)), Position::new(1, 0));
+
+    assert_eq!(grid.get(Position::new(-5, 7)), None);
+    grid.set(Position::new(-5, 7), 3).unwrap();
+    assert_eq!(grid.get(Position::new(-5, 7)), Some(3));
+    assert_eq!(grid.get(Position::new(-6, 7)), Some(0));
+    assert!(grid.is_chunk_loaded(Position::new(-2, 2)));
+    assert_eq!(grid.chunk_count(), 1);
+
+    *grid.get_mut(Position::new(-8, 6)).unwrap() = 1;
+    assert_eq!(grid.chunk(Position::new(-2, 2)).unwrap()[Position::ZERO], 1);
+    assert!(grid.get_mut(Position::new(0, 0)).is_none());
+}
+
+#[test]
+fn lazy_chunk_factory() {
+    let mut grid = ChunkedGrid::with_chunk_factory(
+        RectSize::new(2, 2),
+        Box::new(|chunk: Position| Grid::new(2, 2, chunk.x * 10 + chunk.y)),
+    );
+    assert_eq!(grid.chunk_count(), 0);
+
+    grid.load_chunk(Position::new(3, -1)).unwrap();
+    assert_eq!(grid.get(Position::new(7, -2)), Some(29));
+    grid.set(Position::new(6, -1), 0).unwrap();
+    assert_eq!(grid.get_ref(Position::new(6, -1)), Some(&0));
+
+    let mut tiles: Vec<_> = grid.iter_with_position().map(|(position, &value)| (position, value)).collect();
+    tiles.sort_by_key(|&(position, _)| (position.y, position.x));
+    assert_eq!(tiles, [((6, -2), 29), ((7, -2), 29), ((6, -1), 0), ((7, -1), 29)].map(|(position, value)| (Position::from(position), value)));
+}
+
+#[test]
+fn chunk_factory_with_wrong_size() {
+    let mut grid = ChunkedGrid::with_chunk_factory(
+        RectSize::new(4, 4),
+        Box::new(|chunk: Position| if chunk.x < 0 { Grid::new(2, 3, 0) } else { Grid::new(4, 4, 1) }),
+    );
+    let mismatch = GridError::DimensionMismatch {
+        expected: RectSize::new(4, 4),
+        actual: RectSize::new(2, 3),
+    };
+
+    assert_eq!(grid.set(Position::new(-1, 0), 5), Err(mismatch));
+    assert!(grid.load_chunk(Position::new(-1, 0)).is_err());
+    assert_eq!(grid.chunk_count(), 0);
+    assert_eq!(grid.get(Position::new(-1, 0)), None);
+
+    grid.set(Position::new(1, 0), 5).unwrap();
+    assert_eq!(grid.get(Position::new(0, 0)), Some(1));
+}
+
+#[test]
+fn unload_callback() {
+    // the callback doesn't have to be thread safe
+    let saved = Rc::new(RefCell::new(vec![]));
+    let mut grid = ChunkedGrid::new(RectSize::new(8, 8), '.');
+    let saved_chunks = saved.clone();
+    grid.set_unload_callback(Box::new(move |chunk, tiles: Grid<char>| saved_chunks.borrow_mut().push((chunk, tiles))));
+
+    for x in -2..3 {
+        grid.set(Position::new(x * 8, 0), '#').unwrap();
+    }
+    assert_eq!(grid.unload_chunks(&|chunk, _| chunk.x.abs() > 1), 2);
+    assert!(!grid.unload_chunk(Position::new(5, 5)));
+    assert!(grid.unload_chunk(Position::new(0, 0)));
+    assert_eq!(grid.chunk_count(), 2);
+
+    let mut saved_positions: Vec<_> = saved
+        .borrow()
+        .iter()
+        .map(|(chunk, tiles)| {
+            assert_eq!(tiles[Position::ZERO], '#');
+            chunk.x
+        })
+        .collect();
+    saved_positions.sort_unstable();
+    assert_eq!(saved_positions, [-2, 0, 2]);
+
+    grid.unload_all();
+    assert_eq!(grid.chunk_count(), 0);
+    assert_eq!(saved.borrow().len(), 5);
+}
+
+#[test]
+fn pattern_across_chunks() {
+    let mut grid = ChunkedGrid::new(RectSize::new(2, 2), 1);
+    grid.set(Position::new(0, 0), 5).unwrap();
+    grid.set(Position::new(-1, 0), 2).unwrap();
+
+    let mut values = grid.get_values_from_pattern(Position::new(0, 0), true, adjacent_pattern().as_ref()).into_vec();
+    values.sort_unstable();
+    // the chunk above is not loaded
+    assert_eq!(values, [1, 1, 2, 5]);
+}
@@ -0,0 +1,7 @@
+use xs_games_rs::dim2::grid::Grid;
+
+/// grid of characters with one string per row, e.g. `#` for walls and `.` for floor
+pub fn parse_map(rows: &[&str]) -> Grid<char> {
+    let values: Vec<char> = rows.iter().flat_map(|row| row.chars()).collect();
+    Grid::with_preset_values(rows[0].len(), rows.len(), values.into_boxed_slice())
+}
@@ -32,7 +32,7 @@ fn confirm_cached() {
 
     let call_counter = |_: usize| {
         COUNTER.tick();
-        return 1;
+        1
     };
 
     let cache = FactoryCache::new(BTreeMap::new(), Box::new(call_counter));
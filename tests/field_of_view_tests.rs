@@ -0,0 +1,68 @@
+mod common;
+
+use common::parse_map;
+use xs_games_rs::dim2::grid::{
+    field_of_view::{get_field_of_view, get_visible_positions},
+    Grid, Position,
+};
+
+fn is_wall(tile: &char) -> bool {
+    *tile == '#'
+}
+
+#[test]
+fn open_room_fully_visible() {
+    let grid = parse_map(&[".....", ".....", ".....", ".....", "....."]);
+    let visible = get_field_of_view(&grid, Position::new(2, 2), 5, &is_wall);
+    assert!(visible.iter().all(|&seen| seen));
+}
+
+#[test]
+fn radius_limits_sight() {
+    let grid = Grid::new(11, 11, '.');
+    let origin = Position::new(5, 5);
+    let visible = get_visible_positions(&grid, origin, 3, &is_wall);
+
+    assert!(visible.contains(&origin));
+    assert!(visible.contains(&Position::new(8, 5)));
+    assert!(visible.contains(&Position::new(7, 7)));
+    assert!(!visible.contains(&Position::new(9, 5)));
+    assert!(!visible.contains(&Position::new(8, 8)));
+    assert!(visible.iter().all(|position| position.distance(origin) <= 3.));
+}
+
+#[test]
+fn walls_cast_shadows() {
+    let grid = parse_map(&[".......", ".......", "...#...", ".......", "......."]);
+    let visible = get_field_of_view(&grid, Position::new(3, 4), 10, &is_wall);
+
+    assert!(visible[Position::new(3, 2)]);
+    assert!(!visible[Position::new(3, 1)]);
+    assert!(!visible[Position::new(3, 0)]);
+    assert!(visible[Position::new(0, 0)]);
+    assert!(visible[Position::new(6, 0)]);
+}
+
+#[test]
+fn closed_room_hides_outside() {
+    let grid = parse_map(&["#####...", "#...#...", "#...#...", "#####..."]);
+    let visible = get_visible_positions(&grid, Position::new(2, 1), 10, &is_wall);
+
+    assert_eq!(visible.len(), 20);
+    assert!(visible.iter().all(|position| position.x <= 4));
+}
+
+#[test]
+fn visibility_is_symmetric() {
+    let grid = parse_map(&["..#.......", "....#..#..", ".#........", "......#...", "...#......", "........#.", ".#...#...."]);
+    let floors: Vec<Position> = grid.iter_with_position().filter(|(_, tile)| !is_wall(tile)).map(|(pos, _)| pos).collect();
+
+    for &a in &floors {
+        let seen_from_a = get_field_of_view(&grid, a, 20, &is_wall);
+        for &b in &floors {
+            if seen_from_a[b] {
+                assert!(get_field_of_view(&grid, b, 20, &is_wall)[a], "{a:?} sees {b:?}");
+            }
+        }
+    }
+}
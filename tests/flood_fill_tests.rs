@@ -0,0 +1,61 @@
+mod common;
+
+use common::parse_map;
+use xs_games_rs::dim2::grid::{
+    flood_fill::{flood_fill, flood_fill_equal, get_connected_components, get_equal_components, Connectivity},
+    Position, RectSize,
+};
+
+#[test]
+fn flood_fill_four_connected() {
+    let grid = parse_map(&["..#.", ".#..", "#...", "...."]);
+    let mut region = flood_fill(&grid, Position::new(0, 0), Connectivity::Four, &|&tile| tile == '.');
+    region.sort();
+    assert_eq!(region, [(0, 0), (0, 1), (1, 0)].map(Position::from));
+}
+
+#[test]
+fn flood_fill_eight_connected() {
+    let grid = parse_map(&["..#.", ".#..", "#...", "...."]);
+    let region = flood_fill(&grid, Position::new(0, 0), Connectivity::Eight, &|&tile| tile == '#');
+    assert!(region.is_empty());
+
+    let region = flood_fill_equal(&grid, Position::new(2, 0), Connectivity::Eight);
+    assert_eq!(region.len(), 3);
+    assert!(region.contains(&Position::new(0, 2)));
+}
+
+#[test]
+fn flood_fill_outside_grid() {
+    let grid = parse_map(&["..", ".."]);
+    assert!(flood_fill_equal(&grid, Position::new(2, 0), Connectivity::Four).is_empty());
+}
+
+#[test]
+fn connected_components() {
+    let grid = parse_map(&["aab", "cab", "cbb"]);
+    let components = get_equal_components(&grid, Connectivity::Four);
+
+    assert_eq!(components.regions.len(), 3);
+    assert_eq!(components.labels.as_array(), &[0, 0, 1, 2, 0, 1, 2, 1, 1]);
+
+    let region = components.region_at(Position::new(2, 2)).unwrap();
+    assert_eq!(region.size, 4);
+    assert_eq!(region.first, Position::new(2, 0));
+    assert_eq!(region.bounds_offset, Position::new(1, 0));
+    assert_eq!(region.bounds_size, RectSize::new(2, 3));
+    assert!(components.are_connected(Position::new(0, 0), Position::new(1, 1)));
+    assert!(!components.are_connected(Position::new(0, 0), Position::new(0, 1)));
+}
+
+#[test]
+fn dungeon_connectivity() {
+    let grid = parse_map(&["..#..", "..#..", "#####", "....."]);
+    let is_floor = |&tile: &char| tile == '.';
+    let components = get_connected_components(&grid, Connectivity::Four, &|a, b| is_floor(a) == is_floor(b));
+    let floor_regions = components.regions.iter().filter(|region| is_floor(&grid[region.first])).count();
+    assert_eq!(floor_regions, 3);
+
+    let components = get_connected_components(&grid, Connectivity::Eight, &|a, b| is_floor(a) == is_floor(b));
+    assert_eq!(components.regions.len(), 4);
+}
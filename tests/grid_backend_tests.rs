@@ -0,0 +1,109 @@
+mod common;
+
+use std::collections::HashMap;
+
+use common::parse_map;
+use xs_games_rs::dim2::grid::{
+    field_of_view::get_visible_positions,
+    flood_fill::{flood_fill_equal, get_equal_components, Connectivity},
+    line::draw_line,
+    pathing::{get_distance_map, get_shortest_path, HierarchicalPathfinder, Movement},
+    ChunkedGrid, Grid, GridError, GridRead, GridWrite, Position, RectSize, SparseGrid,
+};
+
+#[test]
+fn pathing_on_sub_view() {
+    let grid = parse_map(&["#######", "#.....#", "#.###.#", "#.....#", "#######"]);
+    let room = grid.sub_view(Position::new(1, 1), RectSize::new(5, 3)).unwrap();
+    let is_pathable = |&tile: &char| tile != '#';
+
+    let (path, cost) = get_shortest_path(&room, Position::new(0, 0), Position::new(4, 2), &is_pathable).unwrap();
+    assert_eq!(cost, 6);
+    assert!(path.iter().all(|&position| room.get(position) == Some('.')));
+    assert!(get_shortest_path(&room, Position::new(0, 0), Position::new(5, 2), &is_pathable).is_none());
+
+    let distances = get_distance_map(&room, &[Position::new(0, 0)], &is_pathable);
+    assert_eq!(distances.size(), room.size());
+    assert_eq!(distances[Position::new(4, 2)], Some(6));
+    assert_eq!(distances[Position::new(2, 1)], None);
+
+    let tile_cost = |&tile: &char| (tile != '#').then_some(1);
+    let pathfinder = HierarchicalPathfinder::new(&room, 2, Movement::Orthogonal, &tile_cost);
+    assert_eq!(pathfinder.get_path(&room, Position::new(0, 0), Position::new(4, 2), &tile_cost).unwrap().1, 6);
+}
+
+#[test]
+fn non_copy_tiles() {
+    let names = ["grass", "grass", "wall", "grass", "water", "wall"].map(String::from);
+    let grid = Grid::with_preset_values(3, 2, Box::new(names));
+
+    let mut region = flood_fill_equal(&grid, Position::new(0, 0), Connectivity::Four);
+    region.sort_by_key(|position| (position.y, position.x));
+    assert_eq!(region, [Position::new(0, 0), Position::new(1, 0), Position::new(0, 1)]);
+    assert_eq!(get_equal_components(&grid, Connectivity::Four).regions.len(), 3);
+
+    let is_pathable = |tile: &String| tile != "wall";
+    assert_eq!(get_shortest_path(&grid, Position::new(0, 1), Position::new(1, 1), &is_pathable).unwrap().1, 1);
+    let visible = get_visible_positions(&grid, Position::new(0, 0), 3, &|tile| tile == "wall");
+    assert!(visible.contains(&Position::new(2, 0)));
+    assert_eq!(visible.len(), 6);
+}
+
+#[test]
+fn write_through_grid_write() {
+    let mut grid = Grid::new(4, 4, 0);
+    {
+        let mut view = grid.sub_view_mut(Position::new(1, 1), RectSize::new(2, 2)).unwrap();
+        draw_line(&mut view, Position::new(-1, -1), Position::new(3, 3), 1);
+        assert_eq!(
+            GridWrite::set(&mut view, Position::new(2, 0), 5),
+            Err(GridError::OutOfBounds {
+                position: Position::new(2, 0),
+                size: RectSize::new(2, 2)
+            })
+        );
+    }
+    assert_eq!(grid.iter_with_position().filter(|(_, &value)| value == 1).count(), 2);
+    assert_eq!(grid[Position::new(2, 2)], 1);
+
+    let mut sparse = SparseGrid::new('.');
+    draw_line(&mut sparse, Position::new(-2, 0), Position::new(2, 0), '#');
+    assert_eq!(sparse.stored_count(), 5);
+
+    let mut chunked = ChunkedGrid::new(RectSize::new(2, 2), '.');
+    draw_line(&mut chunked, Position::new(-2, -2), Position::new(1, 1), '#');
+    assert_eq!(chunked.chunk_count(), 2);
+    assert_eq!(GridRead::get(&chunked, Position::new(-1, -1)), Some('#'));
+    assert_eq!(GridRead::bounds(&chunked), None);
+}
+
+struct PlacedTiles(HashMap<Position, char>);
+
+impl GridRead for PlacedTiles {
+    type Tile = char;
+
+    fn bounds(&self) -> Option<RectSize> {
+        None
+    }
+
+    fn get_ref(&self, position: Position) -> Option<&char> {
+        self.0.get(&position)
+    }
+}
+
+impl GridWrite for PlacedTiles {
+    fn get_mut(&mut self, position: Position) -> Option<&mut char> {
+        self.0.get_mut(&position)
+    }
+}
+
+#[test]
+fn default_set_on_unbounded_grid() {
+    let mut tiles = PlacedTiles(HashMap::from([(Position::new(-5, 3), '.')]));
+    assert_eq!(GridWrite::set(&mut tiles, Position::new(-5, 3), '#'), Ok(()));
+    assert_eq!(tiles.get(Position::new(-5, 3)), Some('#'));
+    assert_eq!(
+        GridWrite::set(&mut tiles, Position::new(0, 0), '#'),
+        Err(GridError::NotWritable { position: Position::new(0, 0) })
+    );
+}
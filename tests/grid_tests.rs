@@ -0,0 +1,386 @@
+use test_case::test_case;
+use xs_games_rs::dim2::grid::{
+    patterns::{adjacent_pattern, get_grid_values_from_pattern, square_pattern},
+    Anchor, Direction, Grid, GridError, Position, RectSize, Rotation, Topology,
+};
+
+#[test]
+fn set_out_of_bounds() {
+    let mut grid = Grid::new(3, 2, 0);
+    assert_eq!(grid.set(Position::new(2, 1), 5), Ok(()));
+    assert_eq!(grid[Position::new(2, 1)], 5);
+    assert_eq!(
+        grid.set(Position::new(3, 0), 1),
+        Err(GridError::OutOfBounds {
+            position: Position::new(3, 0),
+            size: RectSize::new(3, 2)
+        })
+    );
+}
+
+#[test]
+fn swap_values() {
+    let mut grid = Grid::with_preset_values(2, 1, Box::new([1, 2]));
+    grid.swap(Position::new(0, 0), Position::new(1, 0)).unwrap();
+    assert_eq!(grid.as_array(), &[2, 1]);
+    assert!(grid.swap(Position::new(0, 0), Position::new(-1, 0)).is_err());
+    assert_eq!(grid.as_array(), &[2, 1]);
+}
+
+#[test]
+fn sub_grid() {
+    let grid = Grid::with_preset_values(3, 3, (0..9).collect());
+    let sub_grid = grid.get_sub_grid(Position::new(1, 1), RectSize::new(2, 2)).unwrap();
+    assert_eq!(sub_grid.as_array(), &[4, 5, 7, 8]);
+
+    let error = grid.get_sub_grid(Position::new(2, 1), RectSize::new(2, 2)).unwrap_err();
+    assert_eq!(
+        error,
+        GridError::OutOfBounds {
+            position: Position::new(3, 1),
+            size: RectSize::new(3, 3)
+        }
+    );
+}
+
+#[test]
+fn try_with_preset_values() {
+    assert!(Grid::try_with_preset_values(2, 2, Box::new([0; 4])).is_ok());
+
+    let error = Grid::try_with_preset_values(2, 3, Box::new([0; 4])).unwrap_err();
+    assert_eq!(
+        error,
+        GridError::SizeMismatch {
+            size: RectSize::new(2, 3),
+            value_count: 4
+        }
+    );
+    assert_eq!(error.to_string(), "a grid of size 2x3 requires 6 values, got 4");
+}
+
+#[test]
+#[should_panic]
+fn with_preset_values_size_mismatch() {
+    Grid::with_preset_values(2, 3, Box::new([0; 4]));
+}
+
+/// 3x2 grid
+/// ```
+/// 0 1 2
+/// 3 4 5
+/// ```
+fn create_test_grid() -> Grid<i32> {
+    Grid::with_preset_values(3, 2, (0..6).collect())
+}
+
+#[test_case(Rotation::None, 3, 2, &[0, 1, 2, 3, 4, 5]; "none")]
+#[test_case(Rotation::Clockwise90, 2, 3, &[3, 0, 4, 1, 5, 2]; "clockwise 90")]
+#[test_case(Rotation::Clockwise180, 3, 2, &[5, 4, 3, 2, 1, 0]; "clockwise 180")]
+#[test_case(Rotation::Clockwise270, 2, 3, &[2, 5, 1, 4, 0, 3]; "clockwise 270")]
+fn rotate(rotation: Rotation, width: usize, height: usize, expected: &[i32]) {
+    let grid = create_test_grid();
+    let rotated = grid.rotated(rotation);
+    assert_eq!(rotated.size(), RectSize::new(width, height));
+    assert_eq!(rotated.as_array(), expected);
+
+    let mut in_place = grid.clone();
+    in_place.rotate(rotation);
+    assert_eq!(in_place, rotated);
+
+    in_place.rotate(rotation.inverse());
+    assert_eq!(in_place, grid);
+}
+
+#[test]
+fn flip_and_transpose() {
+    let grid = create_test_grid();
+    assert_eq!(grid.flipped_horizontally().as_array(), &[2, 1, 0, 5, 4, 3]);
+    assert_eq!(grid.flipped_vertically().as_array(), &[3, 4, 5, 0, 1, 2]);
+
+    let transposed = grid.transposed();
+    assert_eq!(transposed.size(), RectSize::new(2, 3));
+    assert_eq!(transposed.as_array(), &[0, 3, 1, 4, 2, 5]);
+
+    let mut square = Grid::with_preset_values(3, 3, (0..9).collect::<Vec<_>>().into_boxed_slice());
+    square.flip_vertically();
+    assert_eq!(square.as_array(), &[6, 7, 8, 3, 4, 5, 0, 1, 2]);
+    square.transpose();
+    assert_eq!(square.as_array(), &[6, 3, 0, 7, 4, 1, 8, 5, 2]);
+}
+
+#[test]
+fn rotate_non_copy_values() {
+    let mut grid = Grid::with_preset_values(2, 1, Box::new([String::from("a"), String::from("b")]));
+    grid.rotate(Rotation::Clockwise90);
+    assert_eq!(grid.get_ref(Position::new(0, 1)).unwrap(), "b");
+}
+
+#[test]
+fn rotate_direction() {
+    assert_eq!(Direction::Up.rotated(Rotation::Clockwise90), Direction::Right);
+    assert_eq!(Direction::DownLeft.rotated(Rotation::Clockwise180), Direction::UpRight);
+    assert_eq!(Direction::Left.rotated(Rotation::Clockwise270), Direction::Down);
+}
+
+#[test_case(Anchor::TopLeft, (0, 0), &[0, 1, 2, 9, 3, 4, 5, 9, 9, 9, 9, 9]; "top left")]
+#[test_case(Anchor::Center, (0, 0), &[0, 1, 2, 9, 3, 4, 5, 9, 9, 9, 9, 9]; "center rounds towards top left")]
+#[test_case(Anchor::BottomRight, (1, 1), &[9, 9, 9, 9, 9, 0, 1, 2, 9, 3, 4, 5]; "bottom right")]
+#[test_case(Anchor::Bottom, (0, 1), &[9, 9, 9, 9, 0, 1, 2, 9, 3, 4, 5, 9]; "bottom")]
+fn resize_grow(anchor: Anchor, expected_offset: (i64, i64), expected: &[i32]) {
+    let mut grid = create_test_grid();
+    let offset = grid.resize(RectSize::new(4, 3), anchor, 9);
+    assert_eq!(offset, Position::from(expected_offset));
+    assert_eq!(grid.size(), RectSize::new(4, 3));
+    assert_eq!(grid.as_array(), expected);
+}
+
+#[test]
+fn resize_shrink() {
+    let mut grid = create_test_grid();
+    let offset = grid.resize(RectSize::new(1, 1), Anchor::Center, 9);
+    assert_eq!(offset, Position::new(-1, -1));
+    assert_eq!(grid.as_array(), &[4]);
+
+    let mut grid = create_test_grid();
+    grid.resize(RectSize::new(0, 2), Anchor::Right, 9);
+    assert!(grid.is_empty());
+}
+
+#[test]
+fn crop_to_content() {
+    let mut grid = Grid::with_preset_values(4, 4, Box::new([0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0]));
+    assert_eq!(grid.crop_to_content(&|&value| value != 0), Some(Position::new(1, 1)));
+    assert_eq!(grid.size(), RectSize::new(2, 2));
+    assert_eq!(grid.as_array(), &[1, 0, 0, 2]);
+
+    let mut empty = Grid::new(3, 3, 0);
+    assert_eq!(empty.crop_to_content(&|&value| value != 0), None);
+    assert_eq!(empty.size(), RectSize::new(3, 3));
+}
+
+#[test]
+fn paste_clipped() {
+    let mut board = Grid::new(3, 3, 0);
+    let piece = Grid::new(2, 2, 1);
+
+    let written = board.paste(&piece, Position::new(2, -1));
+    assert_eq!(written, vec![Position::new(2, 0)]);
+    assert_eq!(board.as_array(), &[0, 0, 1, 0, 0, 0, 0, 0, 0]);
+
+    assert!(board.paste(&piece, Position::new(3, 3)).is_empty());
+}
+
+#[test]
+fn paste_clipped_on_torus() {
+    let mut board = Grid::new(3, 3, 0).with_topology(Topology::Torus);
+    let piece = Grid::new(2, 2, 1);
+
+    let written = board.paste(&piece, Position::new(2, -1));
+    assert_eq!(written, vec![Position::new(2, 0)]);
+    assert_eq!(board.as_array(), &[0, 0, 1, 0, 0, 0, 0, 0, 0]);
+}
+
+#[test]
+fn quarter_turns_swap_wrapping_edges() {
+    let grid = Grid::new(3, 2, 0).with_topology(Topology::WrapHorizontal);
+    assert_eq!(grid.rotated(Rotation::Clockwise90).topology(), Topology::WrapVertical);
+    assert_eq!(grid.rotated(Rotation::Clockwise180).topology(), Topology::WrapHorizontal);
+    assert_eq!(grid.rotated(Rotation::Clockwise270).topology(), Topology::WrapVertical);
+
+    let mut transposed = grid.with_topology(Topology::WrapVertical);
+    transposed.transpose();
+    assert_eq!(transposed.topology(), Topology::WrapHorizontal);
+    assert_eq!(transposed.get(Position::new(-1, 0)), transposed.get(Position::new(1, 0)));
+}
+
+#[test]
+fn paste_transparent() {
+    let mut board = Grid::new(3, 2, 5);
+    let piece = Grid::with_preset_values(2, 2, Box::new([0, 1, 1, 1]));
+
+    let written = board.paste_transparent(&piece, Position::new(1, 0), &|&value| value == 0);
+    assert_eq!(written, [(2, 0), (1, 1), (2, 1)].map(Position::from));
+    assert_eq!(board.as_array(), &[5, 5, 1, 5, 1, 1]);
+}
+
+#[test]
+fn paste_with_merge() {
+    let mut occupancy = Grid::new(3, 1, 0u32);
+    let piece = Grid::new(2, 1, true);
+
+    occupancy.paste_with(&piece, Position::new(0, 0), &mut |count, &occupied| {
+        *count += u32::from(occupied);
+        occupied
+    });
+    let written = occupancy.paste_with(&piece, Position::new(1, 0), &mut |count, &occupied| {
+        *count += u32::from(occupied);
+        occupied
+    });
+    assert_eq!(written.len(), 2);
+    assert_eq!(occupancy.as_array(), &[1, 2, 1]);
+}
+
+#[test]
+fn sub_view_local_coordinates() {
+    let grid = Grid::with_preset_values(4, 3, (0..12).collect());
+    let view = grid.sub_view(Position::new(1, 1), RectSize::new(3, 2)).unwrap();
+    assert_eq!(view.get(Position::new(0, 0)), Some(5));
+    assert_eq!(view.get(Position::new(2, 1)), Some(11));
+    assert_eq!(view.get(Position::new(3, 0)), None);
+    assert_eq!(view.to_grid().as_array(), &[5, 6, 7, 9, 10, 11]);
+
+    let nested = view.sub_view(Position::new(1, 0), RectSize::new(2, 2)).unwrap();
+    let values: Vec<_> = nested.iter_with_position().map(|(position, &value)| (position, value)).collect();
+    assert_eq!(values, [((0, 0), 6), ((1, 0), 7), ((0, 1), 10), ((1, 1), 11)].map(|(position, value)| (Position::from(position), value)));
+}
+
+#[test]
+fn sub_view_out_of_bounds() {
+    let grid = Grid::with_preset_values(3, 3, (0..9).collect());
+    assert_eq!(
+        grid.sub_view(Position::new(2, 1), RectSize::new(2, 2)).unwrap_err(),
+        GridError::OutOfBounds {
+            position: Position::new(3, 1),
+            size: RectSize::new(3, 3)
+        }
+    );
+    assert!(grid.sub_view(Position::new(0, 2), RectSize::new(1, 2)).is_err());
+    assert!(grid.sub_view(Position::new(3, 3), RectSize::new(0, 0)).unwrap().is_empty());
+}
+
+#[test]
+fn sub_view_mut_writes_through() {
+    let mut grid = Grid::new(4, 3, 0);
+    let mut view = grid.sub_view_mut(Position::new(1, 1), RectSize::new(2, 2)).unwrap();
+    for (position, value) in view.iter_mut_with_position() {
+        *value = position.x + 10 * position.y + 1;
+    }
+    let mut nested = view.sub_view_mut(Position::new(1, 1), RectSize::new(1, 1)).unwrap();
+    nested.set(Position::new(0, 0), 99).unwrap();
+    assert!(nested.set(Position::new(1, 0), 0).is_err());
+
+    assert_eq!(grid.as_array(), &[0, 0, 0, 0, 0, 1, 2, 0, 0, 11, 99, 0]);
+}
+
+#[test]
+fn split_view_mut() {
+    let mut grid = Grid::new(4, 2, 0);
+    let (mut left, mut right) = grid.view_mut().split_at_column(1);
+    left.iter_mut().for_each(|value| *value = 1);
+    let (mut top_right, mut bottom_right) = right.sub_view_mut(Position::ZERO, right.size()).unwrap().split_at_row(1);
+    top_right.iter_mut().for_each(|value| *value = 2);
+    *bottom_right.get_mut(Position::new(2, 0)).unwrap() = 3;
+    assert_eq!(left.width(), 1);
+    assert_eq!(right.width(), 3);
+
+    assert_eq!(grid.as_array(), &[1, 2, 2, 2, 1, 0, 0, 3]);
+}
+
+#[test]
+fn read_split_view_while_writing_other_half() {
+    let mut grid = Grid::with_preset_values(4, 2, Box::new([1, 2, 3, 4, 5, 6, 7, 8]));
+    let (left, mut right) = grid.view_mut().split_at_column(2);
+    // reading one half must not touch the columns borrowed by the other one
+    let tile = right.get_mut(Position::new(0, 1)).unwrap();
+    assert_eq!(left.as_view().iter().sum::<i32>(), 14);
+    assert_eq!(left.sub_view(Position::new(1, 0), RectSize::new(1, 2)).unwrap().to_grid().as_array(), &[2, 6]);
+    *tile = 0;
+
+    std::thread::scope(|scope| {
+        scope.spawn(|| assert_eq!(left.to_grid().as_array(), &[1, 2, 5, 6]));
+        scope.spawn(|| right.iter_mut().for_each(|value| *value *= 10));
+    });
+    assert_eq!(grid.as_array(), &[1, 2, 30, 40, 5, 6, 0, 80]);
+}
+
+#[test]
+fn rows_and_columns() {
+    let mut grid = create_test_grid();
+    assert_eq!(grid.row(1), Some(&[3, 4, 5][..]));
+    assert_eq!(grid.row(2), None);
+    assert_eq!(grid.column(2).unwrap().copied().collect::<Vec<_>>(), [2, 5]);
+    assert!(grid.column(3).is_none());
+    assert_eq!(grid.rows().map(|row| row.iter().sum::<i32>()).collect::<Vec<_>>(), [3, 12]);
+    assert_eq!(grid.columns().map(|column| column.sum::<i32>()).collect::<Vec<_>>(), [3, 5, 7]);
+
+    grid.row_mut(0).unwrap().reverse();
+    grid.column_mut(1).unwrap().for_each(|value| *value *= 10);
+    assert_eq!(grid.as_array(), &[2, 10, 0, 3, 40, 5]);
+
+    for (y, row) in grid.rows_mut().enumerate() {
+        row[0] = y as i32;
+    }
+    for (x, column) in grid.columns_mut().enumerate() {
+        column.for_each(|value| *value += 100 * x as i32);
+    }
+    assert_eq!(grid.as_array(), &[0, 110, 200, 1, 140, 205]);
+}
+
+#[test]
+fn connect_four_column_scan() {
+    let mut board = Grid::new(4, 5, 0);
+    for y in 1..5 {
+        board.set(Position::new(2, y), 1).unwrap();
+    }
+    let winner = board.columns().position(|column| column.collect::<Vec<_>>().windows(4).any(|cells| cells.iter().all(|&&cell| cell == 1)));
+    assert_eq!(winner, Some(2));
+}
+
+#[test]
+fn windows() {
+    let grid = Grid::with_preset_values(3, 3, (0..9).collect());
+    let windows: Vec<_> = grid.windows(RectSize::new(2, 2)).map(|(offset, window)| (offset, window.to_grid().into_array())).collect();
+    assert_eq!(windows.len(), 4);
+    assert_eq!(windows[3], (Position::new(1, 1), Box::from([4, 5, 7, 8])));
+    assert_eq!(grid.windows(RectSize::new(4, 1)).count(), 0);
+    assert_eq!(grid.windows(RectSize::new(0, 1)).count(), 0);
+}
+
+#[test]
+fn neighbors() {
+    let grid = create_test_grid();
+    let neighbors: Vec<_> = grid.neighbors(Position::new(0, 0), square_pattern(1).as_ref()).map(|(position, &value)| (position, value)).collect();
+    assert_eq!(neighbors, [((1, 0), 1), ((0, 1), 3), ((1, 1), 4)].map(|(position, value)| (Position::from(position), value)));
+}
+
+#[test]
+fn neighbors_on_torus() {
+    let grid = Grid::with_preset_values(3, 3, (0..9).collect()).with_topology(Topology::Torus);
+    let mut neighbors: Vec<_> = grid.neighbors(Position::new(0, 0), adjacent_pattern().as_ref()).map(|(position, &value)| (position, value)).collect();
+    neighbors.sort_by_key(|&(_, value)| value);
+    // the neighbours left of and above the corner are on the opposite edges
+    assert_eq!(neighbors, [((1, 0), 1), ((2, 0), 2), ((0, 1), 3), ((0, 2), 6)].map(|(position, value)| (Position::from(position), value)));
+}
+
+#[test]
+fn topology_wraps_access() {
+    let mut grid = create_test_grid().with_topology(Topology::Torus);
+    assert_eq!(grid.get(Position::new(-1, 0)), Some(2));
+    assert_eq!(grid.get(Position::new(3, 3)), Some(3));
+    grid.set(Position::new(-3, -1), 9).unwrap();
+    assert_eq!(grid[Position::new(0, 1)], 9);
+
+    grid.set_topology(Topology::WrapHorizontal);
+    assert_eq!(grid.get(Position::new(4, 0)), Some(1));
+    assert_eq!(grid.get(Position::new(0, 2)), None);
+    assert_eq!(grid.wrap_position(Position::new(-1, 1)), Some(Position::new(2, 1)));
+}
+
+#[test]
+fn topology_wraps_patterns() {
+    let grid = create_test_grid();
+    assert_eq!(get_grid_values_from_pattern(&grid, Position::ZERO, false, adjacent_pattern().as_ref()).len(), 2);
+
+    let torus = grid.with_topology(Topology::Torus);
+    let mut values = get_grid_values_from_pattern(&torus, Position::ZERO, false, adjacent_pattern().as_ref()).into_vec();
+    values.sort_unstable();
+    assert_eq!(values, [1, 2, 3, 3]);
+}
+
+#[test]
+fn topology_shortest_offset() {
+    let size = RectSize::new(10, 4);
+    assert_eq!(Topology::Torus.offset(Position::new(1, 0), Position::new(9, 3), size), Position::new(-2, -1));
+    assert_eq!(Topology::WrapVertical.offset(Position::new(1, 0), Position::new(9, 3), size), Position::new(8, -1));
+    assert_eq!(Topology::Bounded.offset(Position::new(1, 0), Position::new(9, 3), size), Position::new(8, 3));
+}
@@ -0,0 +1,188 @@
+mod common;
+
+use std::collections::HashSet;
+
+use common::parse_map;
+use test_case::test_case;
+use xs_games_rs::{
+    dim2::{
+        grid::{Grid, GridError, Position, RectSize, Topology},
+        hex::{get_shortest_hex_path, get_shortest_weighted_hex_path, hex_path_exists, Hex, HexGrid, HexOrientation, HexScreenTranslation, OffsetLayout},
+    },
+    ScreenView,
+};
+
+#[test]
+fn cube_coordinates_and_distance() {
+    let hex = Hex::from_cube(2, -3, 1);
+    assert_eq!(hex, Hex::new(2, -3));
+    assert_eq!(hex.cube(), (2, -3, 1));
+    assert_eq!(hex.distance(Hex::ZERO), 3);
+    assert_eq!(Hex::new(-2, 4).distance(Hex::new(1, -1)), 5);
+
+    for neighbor in hex.neighbors() {
+        assert_eq!(hex.distance(neighbor), 1);
+    }
+    assert_eq!(hex.neighbor(6), hex.neighbor(0));
+    assert_eq!(Hex::round(0.9, -0.2), Hex::new(1, 0));
+}
+
+#[test]
+fn rings_and_spirals() {
+    let center = Hex::new(3, -1);
+    assert_eq!(center.ring(0), [center]);
+    for radius in 1..5 {
+        let ring = center.ring(radius);
+        assert_eq!(ring.len(), 6 * radius);
+        assert!(ring.iter().all(|hex| hex.distance(center) == radius as i64));
+        assert_eq!(ring.iter().collect::<HashSet<_>>().len(), ring.len());
+        for pair in ring.windows(2) {
+            assert_eq!(pair[0].distance(pair[1]), 1);
+        }
+    }
+
+    let spiral = center.spiral(3);
+    assert_eq!(spiral.len(), 37);
+    assert_eq!(spiral[0], center);
+    assert_eq!(spiral.iter().collect::<HashSet<_>>().len(), 37);
+}
+
+#[test]
+fn lines() {
+    let line = Hex::new(0, 0).line_to(Hex::new(4, -2));
+    assert_eq!(line.len(), 5);
+    assert_eq!(line.first(), Some(&Hex::new(0, 0)));
+    assert_eq!(line.last(), Some(&Hex::new(4, -2)));
+    for pair in line.windows(2) {
+        assert_eq!(pair[0].distance(pair[1]), 1);
+    }
+
+    // exactly along the edges between hexes
+    let line = Hex::new(0, 0).line_to(Hex::new(2, -4));
+    assert_eq!(line.len(), 5);
+    assert!(line.windows(2).all(|pair| pair[0].distance(pair[1]) == 1));
+    assert_eq!(Hex::new(1, 1).line_to(Hex::new(1, 1)), [Hex::new(1, 1)]);
+}
+
+#[test_case(OffsetLayout::OddRows)]
+#[test_case(OffsetLayout::EvenRows)]
+#[test_case(OffsetLayout::OddColumns)]
+#[test_case(OffsetLayout::EvenColumns)]
+fn offset_round_trip(layout: OffsetLayout) {
+    for y in -5..5 {
+        for x in -5..5 {
+            let position = Position::new(x, y);
+            assert_eq!(layout.to_position(layout.to_hex(position)), position);
+        }
+    }
+
+    // neighbours in the offset grid are adjacent in the grid as well
+    for hex in Hex::new(1, 1).spiral(4) {
+        for neighbor in hex.neighbors() {
+            let offset = layout.to_position(neighbor) - layout.to_position(hex);
+            assert!(offset.x.abs() <= 1 && offset.y.abs() <= 1, "{hex:?} {neighbor:?} {layout:?}");
+        }
+    }
+}
+
+#[test]
+fn odd_row_layout() {
+    let layout = OffsetLayout::OddRows;
+    assert_eq!(layout.to_position(Hex::new(0, 1)), Position::new(0, 1));
+    assert_eq!(layout.to_position(Hex::new(-1, 2)), Position::new(0, 2));
+    assert_eq!(layout.to_hex(Position::new(3, 3)), Hex::new(2, 3));
+    assert_eq!(OffsetLayout::EvenRows.to_hex(Position::new(3, 3)), Hex::new(1, 3));
+}
+
+#[test]
+fn hex_grid_access() {
+    let mut board = HexGrid::new(Grid::new(4, 3, 0), OffsetLayout::OddRows);
+    board.set(Hex::new(-1, 2), 5).unwrap();
+    assert_eq!(board.grid()[Position::new(0, 2)], 5);
+    assert_eq!(board.get(Hex::new(-1, 2)), Some(5));
+    assert_eq!(board.get(Hex::new(-1, 1)), None);
+    assert_eq!(
+        board.set(Hex::new(4, 0), 1),
+        Err(GridError::OutOfBounds {
+            position: Position::new(4, 0),
+            size: RectSize::new(4, 3)
+        })
+    );
+
+    assert_eq!(board.neighbors(Hex::new(0, 0)).count(), 2);
+    assert_eq!(board.neighbors(Hex::new(1, 1)).count(), 6);
+    assert_eq!(board.iter_with_hex().filter(|(_, &value)| value == 5).map(|(hex, _)| hex).collect::<Vec<_>>(), [Hex::new(-1, 2)]);
+}
+
+#[test]
+fn hex_pathing() {
+    // '#' is a mountain, '~' a swamp
+    let rows = ["......", ".####.", ".~~~..", "......"];
+    let board = HexGrid::new(parse_map(&rows), OffsetLayout::OddRows);
+    let start = board.to_hex(Position::new(0, 0));
+    let end = board.to_hex(Position::new(3, 3));
+
+    let (path, cost) = get_shortest_hex_path(&board, start, end, &|&tile| tile != '#').unwrap();
+    assert_eq!(cost, path.len() as i64 - 1);
+    assert!(path.windows(2).all(|pair| pair[0].distance(pair[1]) == 1));
+    assert!(path.iter().all(|&hex| board.get(hex).is_some_and(|tile| tile != '#')));
+
+    let tile_cost = |&tile: &char| match tile {
+        '#' => None,
+        '~' => Some(5),
+        _ => Some(1),
+    };
+    let (weighted_path, weighted_cost) = get_shortest_weighted_hex_path(&board, start, end, &tile_cost).unwrap();
+    assert!(weighted_cost >= cost);
+    assert!(weighted_path.iter().all(|&hex| board.get(hex) != Some('~')));
+
+    assert!(!hex_path_exists(&board, start, Hex::new(10, 0), &|&tile| tile != '#'));
+}
+
+#[test]
+fn wrapping_grid_is_not_wrapped() {
+    // the tile at (1, 1) is surrounded by mountains
+    let rows = [".##...", "#.#...", ".##...", "......"];
+    let mut board = HexGrid::new(parse_map(&rows).with_topology(Topology::Torus), OffsetLayout::OddRows);
+    assert_eq!(board.grid().topology(), Topology::Bounded);
+    // changing the topology afterwards doesn't make positions outside of the board valid either
+    board.grid_mut().set_topology(Topology::Torus);
+    assert!(!board.contains(Hex::new(-1, 0)));
+    assert_eq!(board.get(Hex::new(6, 0)), None);
+    assert!(board.set(Hex::new(0, -1), '.').is_err());
+
+    let start = board.to_hex(Position::new(5, 3));
+    let enclosed = board.to_hex(Position::new(1, 1));
+    assert!(!hex_path_exists(&board, start, enclosed, &|&tile| tile != '#'));
+    assert!(!hex_path_exists(&board, start, Hex::new(10, 0), &|&tile| tile != '#'));
+}
+
+#[test_case(HexOrientation::PointyTop)]
+#[test_case(HexOrientation::FlatTop)]
+fn screen_translation_round_trip(orientation: HexOrientation) {
+    let screen_view = ScreenView {
+        offset_x: 10.,
+        offset_y: 20.,
+        width: 500.,
+        height: 400.,
+    };
+    let translation = HexScreenTranslation::new(screen_view, orientation, 20.);
+    let (width, height) = translation.hex_extent();
+    assert_eq!(translation.hex_center_to_screen_position(Hex::ZERO), (10. + width / 2., 20. + height / 2.));
+
+    let layout = match orientation {
+        HexOrientation::PointyTop => OffsetLayout::OddRows,
+        HexOrientation::FlatTop => OffsetLayout::OddColumns,
+    };
+    for position in RectSize::new(5, 5).iter() {
+        let hex = layout.to_hex(position);
+        let (x, y) = translation.hex_center_to_screen_position(hex);
+        assert_eq!(translation.get_hex(x, y), Some(hex));
+        for (corner_x, corner_y) in translation.hex_corners(hex) {
+            // a bit inside of every corner still belongs to the hex
+            let (inner_x, inner_y) = (x + (corner_x - x) * 0.9, y + (corner_y - y) * 0.9);
+            assert_eq!(translation.get_hex(inner_x, inner_y), Some(hex));
+        }
+    }
+    assert_eq!(translation.get_hex(5., 30.), None);
+}
@@ -0,0 +1,120 @@
+use xs_games_rs::dim2::grid::{
+    pathing::{get_shortest_path, get_shortest_weighted_path, Movement},
+    Grid, GridError, LayeredGrid, Position, RectSize,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Grass,
+    Water,
+    Bridge,
+    Tree,
+    Flower,
+}
+
+/// grass with a river in column 2, a bridge at (2, 1) and a tree at (1, 0)
+fn create_test_map() -> LayeredGrid<Tile> {
+    let mut map = LayeredGrid::new(RectSize::new(4, 3));
+    let terrain = map.add_layer("terrain");
+    let objects = map.add_layer("objects");
+    map.add_layer("decoration");
+
+    for position in map.size().iter() {
+        let tile = if position.x == 2 { Tile::Water } else { Tile::Grass };
+        map.set(terrain, position, Some(tile)).unwrap();
+    }
+    map.set(objects, Position::new(2, 1), Some(Tile::Bridge)).unwrap();
+    map.set(objects, Position::new(1, 0), Some(Tile::Tree)).unwrap();
+    map
+}
+
+#[test]
+fn layers_by_name_and_index() {
+    let mut map = create_test_map();
+    assert_eq!(map.layer_count(), 3);
+    assert_eq!(map.layer_index("objects"), Some(1));
+    assert_eq!(map.layer_name(2), Some("decoration"));
+    assert_eq!(map.layer_index("collision"), None);
+
+    assert_eq!(map.get_ref(1, Position::new(2, 1)), Some(&Tile::Bridge));
+    assert_eq!(map.get_ref(1, Position::new(0, 0)), None);
+    assert_eq!(map.get_ref(5, Position::new(0, 0)), None);
+    assert_eq!(
+        map.set(0, Position::new(4, 0), Some(Tile::Grass)),
+        Err(GridError::OutOfBounds {
+            position: Position::new(4, 0),
+            size: RectSize::new(4, 3)
+        })
+    );
+    assert_eq!(
+        map.set(5, Position::new(0, 0), Some(Tile::Grass)),
+        Err(GridError::LayerNotFound { layer: 5, layer_count: 3 })
+    );
+
+    *map.layer_by_name_mut("decoration").unwrap().get_mut(Position::new(0, 2)).unwrap() = Some(Tile::Flower);
+    assert_eq!(map.layers().map(|(name, _)| name).collect::<Vec<_>>(), ["terrain", "objects", "decoration"]);
+    assert_eq!(
+        map.add_layer_grid("too small", Grid::new(2, 2, None)),
+        Err(GridError::DimensionMismatch {
+            expected: RectSize::new(4, 3),
+            actual: RectSize::new(2, 2)
+        })
+    );
+    // same amount of tiles, but transposed
+    let error = map.add_layer_grid("transposed", Grid::new(3, 4, None)).unwrap_err();
+    assert_eq!(error.to_string(), "expected a grid of size 4x3, got 3x4");
+
+    map.rename_layer(2, "details");
+    assert_eq!(map.layer_index("details"), Some(2));
+}
+
+#[test]
+#[should_panic]
+fn rename_missing_layer() {
+    create_test_map().rename_layer(5, "missing");
+}
+
+#[test]
+fn topmost_and_reordering() {
+    let mut map = create_test_map();
+    assert_eq!(map.topmost(Position::new(2, 1)), Some((1, &Tile::Bridge)));
+    assert_eq!(map.topmost(Position::new(0, 0)), Some((0, &Tile::Grass)));
+    assert_eq!(map.topmost(Position::new(-1, 0)), None);
+    assert_eq!(map.tiles_at(Position::new(1, 0)).collect::<Vec<_>>(), [(0, &Tile::Grass), (1, &Tile::Tree)]);
+
+    map.move_layer(0, 2);
+    assert_eq!(map.layer_index("terrain"), Some(2));
+    assert_eq!(map.topmost(Position::new(2, 1)), Some((2, &Tile::Water)));
+
+    map.swap_layers(0, 2);
+    assert_eq!(map.layer_name(0), Some("terrain"));
+    let (name, decoration) = map.remove_layer(1);
+    assert_eq!(name, "decoration");
+    assert!(decoration.iter().all(Option::is_none));
+    assert_eq!(map.layer_index("objects"), Some(1));
+}
+
+#[test]
+fn pathing_from_layers() {
+    let map = create_test_map();
+    let (terrain, objects) = (map.layer_index("terrain").unwrap(), map.layer_index("objects").unwrap());
+
+    let walkable = map.walkability(&|tiles| match tiles[objects] {
+        Some(Tile::Bridge) => true,
+        Some(_) => false,
+        None => tiles[terrain] == Some(&Tile::Grass),
+    });
+    assert_eq!(walkable.count_ones(), 9);
+    let (path, cost) = get_shortest_path(&walkable, Position::new(0, 0), Position::new(3, 0), &|&walkable| walkable).unwrap();
+    assert_eq!(cost, 5);
+    assert!(path.contains(&Position::new(2, 1)));
+
+    let tile_costs = map.combine_layers(&|tiles| match (tiles[terrain], tiles[objects]) {
+        (_, Some(Tile::Bridge)) => Some(1),
+        (_, Some(_)) => None,
+        (Some(Tile::Water), None) => Some(10),
+        _ => Some(1),
+    });
+    let (_, cost) = get_shortest_weighted_path(&tile_costs, Position::new(0, 2), Position::new(3, 2), Movement::Orthogonal, &|&cost| cost).unwrap();
+    assert_eq!(cost, 5);
+}
@@ -0,0 +1,65 @@
+use test_case::test_case;
+use xs_games_rs::dim2::grid::{
+    line::{draw_line, has_line_of_sight, BresenhamLine, SupercoverLine},
+    Grid, Position,
+};
+
+#[test_case((0, 0), (4, 0); "horizontal")]
+#[test_case((2, 5), (2, -3); "vertical upwards")]
+#[test_case((0, 0), (5, 2); "shallow")]
+#[test_case((3, 3), (-2, 7); "steep backwards")]
+#[test_case((1, 1), (1, 1); "single point")]
+fn bresenham_line_properties(from: (i64, i64), to: (i64, i64)) {
+    let (from, to) = (Position::from(from), Position::from(to));
+    let line: Vec<_> = BresenhamLine::new(from, to).collect();
+
+    let distance = to - from;
+    assert_eq!(line.len() as i64, distance.x.abs().max(distance.y.abs()) + 1);
+    assert_eq!((line[0], line[line.len() - 1]), (from, to));
+    for step in line.windows(2) {
+        let offset = step[1] - step[0];
+        assert!(offset.x.abs() <= 1 && offset.y.abs() <= 1 && offset != Position::ZERO);
+    }
+}
+
+#[test]
+fn bresenham_diagonal() {
+    let line: Vec<_> = Position::new(0, 0).line_to(Position::new(-3, 3)).collect();
+    assert_eq!(line, [(0, 0), (-1, 1), (-2, 2), (-3, 3)].map(Position::from));
+}
+
+#[test]
+fn supercover_line() {
+    let line: Vec<_> = SupercoverLine::new(Position::new(0, 0), Position::new(3, 1)).collect();
+    assert_eq!(line, [(0, 0), (1, 0), (2, 0), (1, 1), (2, 1), (3, 1)].map(Position::from));
+
+    let line: Vec<_> = Position::new(0, 0).supercover_line_to(Position::new(0, -2)).collect();
+    assert_eq!(line, [(0, 0), (0, -1), (0, -2)].map(Position::from));
+}
+
+#[test]
+fn supercover_line_through_corners() {
+    let line: Vec<_> = SupercoverLine::new(Position::new(0, 0), Position::new(2, 2)).collect();
+    assert_eq!(line, [(0, 0), (1, 0), (0, 1), (1, 1), (2, 1), (1, 2), (2, 2)].map(Position::from));
+}
+
+#[test]
+fn line_of_sight() {
+    let values: Vec<char> = "......#...........".chars().collect();
+    let grid = Grid::with_preset_values(6, 3, values.into_boxed_slice());
+    let is_blocking = |&tile: &char| tile == '#';
+
+    assert!(has_line_of_sight(&grid, Position::new(0, 0), Position::new(5, 0), &is_blocking));
+    assert!(!has_line_of_sight(&grid, Position::new(0, 0), Position::new(0, 2), &is_blocking));
+    assert!(has_line_of_sight(&grid, Position::new(0, 0), Position::new(0, 1), &is_blocking));
+    assert!(!has_line_of_sight(&grid, Position::new(0, 0), Position::new(6, 0), &is_blocking));
+}
+
+#[test]
+fn draw_line_into_grid() {
+    let mut grid = Grid::new(4, 4, 0);
+    draw_line(&mut grid, Position::new(-1, -1), Position::new(5, 5), 1);
+    for (position, &value) in grid.iter_with_position() {
+        assert_eq!(value == 1, position.x == position.y);
+    }
+}
@@ -0,0 +1,418 @@
+mod common;
+
+use common::parse_map;
+use xs_games_rs::dim2::grid::{
+    pathing::{
+        get_distance_map, get_flow_field, get_jump_point_path, get_reachable_tiles, get_shortest_path, get_shortest_weighted_path, get_weighted_distance_map,
+        HierarchicalPathfinder, IncrementalPathfinder, Movement, OCTILE_DIAGONAL_COST, OCTILE_STRAIGHT_COST,
+    },
+    Direction, Grid, Position, RectSize, Topology,
+};
+
+/// deterministic map with roughly a quarter walls and a few swamps
+fn generate_map(width: usize, height: usize, seed: u64) -> Grid<char> {
+    let mut state = seed;
+    let values: Vec<char> = (0..width * height)
+        .map(|_| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            match (state >> 33) % 8 {
+                0 | 1 => '#',
+                2 => '~',
+                _ => '.',
+            }
+        })
+        .collect();
+    Grid::with_preset_values(width, height, values.into_boxed_slice())
+}
+
+/// asserts that `path` is a valid path in `grid` and returns its cost
+fn path_cost(grid: &Grid<char>, path: &[Position], movement: Movement) -> i64 {
+    path.windows(2)
+        .map(|step| {
+            let offset = grid.topology().offset(step[0], step[1], grid.size());
+            assert!(offset.x.abs() <= 1 && offset.y.abs() <= 1 && (movement.allows_diagonals() || offset.x == 0 || offset.y == 0));
+            terrain_cost(&grid[step[1]]).unwrap() * movement.step_cost(offset)
+        })
+        .sum()
+}
+
+fn terrain_cost(tile: &char) -> Option<i64> {
+    match tile {
+        '#' => None,
+        '~' => Some(3),
+        _ => Some(1),
+    }
+}
+
+#[test]
+fn unweighted_path_unchanged() {
+    let grid = parse_map(&["...", ".#.", "..."]);
+    let (path, cost) = get_shortest_path(&grid, Position::new(0, 0), Position::new(2, 2), &|&tile| tile != '#').unwrap();
+    assert_eq!(cost, 4);
+    assert_eq!(path.len(), 5);
+}
+
+#[test]
+fn weighted_path_avoids_swamp() {
+    let grid = parse_map(&["....", ".~~.", "...."]);
+    let (path, cost) = get_shortest_weighted_path(&grid, Position::new(0, 1), Position::new(3, 1), Movement::Orthogonal, &terrain_cost).unwrap();
+    assert_eq!(cost, 5);
+    assert!(!path.contains(&Position::new(1, 1)));
+}
+
+#[test]
+fn weighted_path_crosses_swamp_when_cheaper() {
+    let grid = parse_map(&[".~.", "###"]);
+    let (path, cost) = get_shortest_weighted_path(&grid, Position::new(0, 0), Position::new(2, 0), Movement::Orthogonal, &terrain_cost).unwrap();
+    assert_eq!(cost, 4);
+    assert_eq!(path, vec![Position::new(0, 0), Position::new(1, 0), Position::new(2, 0)]);
+}
+
+#[test]
+fn diagonal_movement() {
+    let grid = parse_map(&["....", "....", "....", "...."]);
+    let movement = Movement::Diagonal { corner_cutting: true };
+    let (path, cost) = get_shortest_weighted_path(&grid, Position::new(0, 0), Position::new(3, 3), movement, &terrain_cost).unwrap();
+    assert_eq!(cost, 3);
+    assert_eq!(path.len(), 4);
+}
+
+#[test]
+fn octile_movement() {
+    let grid = parse_map(&["....", "....", "...."]);
+    let movement = Movement::Octile { corner_cutting: true };
+    let (_, cost) = get_shortest_weighted_path(&grid, Position::new(0, 0), Position::new(3, 2), movement, &terrain_cost).unwrap();
+    assert_eq!(cost, 2 * OCTILE_DIAGONAL_COST + OCTILE_STRAIGHT_COST);
+}
+
+#[test]
+fn no_corner_cutting() {
+    let grid = parse_map(&[".#", ".."]);
+    let start = Position::new(0, 0);
+    let end = Position::new(1, 1);
+
+    let cutting = Movement::Diagonal { corner_cutting: true };
+    let (path, _) = get_shortest_weighted_path(&grid, start, end, cutting, &terrain_cost).unwrap();
+    assert_eq!(path, vec![start, end]);
+
+    let not_cutting = Movement::Diagonal { corner_cutting: false };
+    let (path, cost) = get_shortest_weighted_path(&grid, start, end, not_cutting, &terrain_cost).unwrap();
+    assert_eq!(path, vec![start, Position::new(0, 1), end]);
+    assert_eq!(cost, 2);
+}
+
+#[test]
+fn no_path() {
+    let grid = parse_map(&[".#.", ".#.", ".#."]);
+    let movement = Movement::Octile { corner_cutting: true };
+    assert!(get_shortest_weighted_path(&grid, Position::new(0, 0), Position::new(2, 2), movement, &terrain_cost).is_none());
+}
+
+#[test]
+fn heuristic_crosses_seams() {
+    let size = RectSize::new(10, 8);
+    let (from, to) = (Position::new(1, 1), Position::new(8, 3));
+    assert_eq!(Movement::Orthogonal.heuristic(from, to, size, Topology::Bounded), 9);
+    assert_eq!(Movement::Orthogonal.heuristic(from, to, size, Topology::WrapHorizontal), 5);
+    assert_eq!(Movement::Diagonal { corner_cutting: true }.heuristic(from, to, size, Topology::Torus), 3);
+    assert_eq!(
+        Movement::Octile { corner_cutting: true }.heuristic(from, to, size, Topology::Torus),
+        OCTILE_DIAGONAL_COST * 2 + OCTILE_STRAIGHT_COST
+    );
+}
+
+#[test]
+fn distance_map_matches_shortest_path() {
+    let grid = parse_map(&["..#..", ".~#..", "..~..", "##.#."]);
+    let goal = Position::new(4, 0);
+
+    for movement in [Movement::Orthogonal, Movement::Diagonal { corner_cutting: false }, Movement::Octile { corner_cutting: true }] {
+        let distances = get_weighted_distance_map(&grid, &[goal], movement, &terrain_cost);
+        for (position, &distance) in distances.iter_with_position() {
+            let path = get_shortest_weighted_path(&grid, position, goal, movement, &terrain_cost);
+            if grid[position] == '#' {
+                assert!(distance.is_none());
+            } else {
+                assert_eq!(distance.map(i64::from), path.map(|(_, cost)| cost), "{movement:?} {position:?}");
+            }
+        }
+    }
+}
+
+#[test]
+fn distance_map_multiple_goals() {
+    let grid = parse_map(&["....."]);
+    let distances = get_distance_map(&grid, &[Position::new(0, 0), Position::new(4, 0)], &|&tile| tile != '#');
+    let expected = [Some(0), Some(1), Some(2), Some(1), Some(0)];
+    assert_eq!(distances.as_array(), &expected);
+}
+
+#[test]
+fn flow_field_leads_to_goal() {
+    let grid = parse_map(&["....#", ".##.#", ".~...", "...#."]);
+    let goal = Position::new(4, 3);
+    let movement = Movement::Octile { corner_cutting: false };
+    let distances = get_weighted_distance_map(&grid, &[goal], movement, &terrain_cost);
+    let flow_field = get_flow_field(&grid, &distances, movement, &terrain_cost);
+
+    assert!(flow_field[goal].is_none());
+    assert!(flow_field[Position::new(4, 0)].is_none());
+
+    for (start, &distance) in distances.iter_with_position() {
+        let Some(distance) = distance else {
+            continue;
+        };
+
+        let mut position = start;
+        let mut cost = 0;
+        while let Some(direction) = flow_field[position] {
+            let next = position.step(direction);
+            cost += terrain_cost(&grid[next]).unwrap() * movement.step_cost(direction.offset());
+            position = next;
+        }
+        assert_eq!(position, goal);
+        assert_eq!(cost, i64::from(distance));
+    }
+}
+
+#[test]
+fn reachable_tiles_within_budget() {
+    let grid = parse_map(&[".....", ".~#..", "....."]);
+    let start = Position::new(0, 0);
+    let reachable = get_reachable_tiles(&grid, start, 3, Movement::Orthogonal, &terrain_cost);
+
+    assert_eq!(reachable.cost(start), Some(0));
+    assert_eq!(reachable.cost(Position::new(3, 0)), Some(3));
+    assert_eq!(reachable.cost(Position::new(1, 2)), Some(3));
+    assert!(!reachable.contains(Position::new(1, 1)));
+    assert!(!reachable.contains(Position::new(4, 0)));
+    assert!(!reachable.contains(Position::new(2, 1)));
+    assert_eq!(reachable.len(), 7);
+
+    for (position, cost) in reachable.iter() {
+        assert!(cost <= 3);
+        let (_, path_cost) = get_shortest_weighted_path(&grid, start, position, Movement::Orthogonal, &terrain_cost).unwrap();
+        assert_eq!(cost, path_cost);
+    }
+}
+
+#[test]
+fn reachable_tiles_path_reconstruction() {
+    let grid = parse_map(&["....", "#~#.", "...."]);
+    let start = Position::new(0, 0);
+    let reachable = get_reachable_tiles(&grid, start, 10, Movement::Orthogonal, &terrain_cost);
+
+    let path = reachable.path_to(Position::new(0, 2)).unwrap();
+    let expected = [(0, 0), (1, 0), (1, 1), (1, 2), (0, 2)].map(Position::from);
+    assert_eq!(path, expected);
+    assert_eq!(reachable.cost(Position::new(0, 2)), Some(6));
+    assert_eq!(reachable.path_to(start), Some(vec![start]));
+    assert!(reachable.path_to(Position::new(0, 1)).is_none());
+}
+
+#[test]
+fn hierarchical_path_matches_astar() {
+    let movements = [Movement::Orthogonal, Movement::Diagonal { corner_cutting: true }, Movement::Octile { corner_cutting: false }];
+    for (seed, movement) in movements.into_iter().enumerate() {
+        let grid = generate_map(30, 27, seed as u64);
+        let pathfinder = HierarchicalPathfinder::new(&grid, 7, movement, &terrain_cost);
+
+        for (start_index, end_index) in [(0, 809), (31, 500), (100, 101), (250, 260), (700, 13), (400, 401)] {
+            let start = Position::new(start_index % 30, start_index / 30);
+            let end = Position::new(end_index % 30, end_index / 30);
+            let expected = get_shortest_weighted_path(&grid, start, end, movement, &terrain_cost);
+            let result = pathfinder.get_path(&grid, start, end, &terrain_cost);
+
+            assert_eq!(result.is_some(), expected.is_some(), "{movement:?} {start:?} {end:?}");
+            if let (Some((path, cost)), Some((_, optimal_cost))) = (result, expected) {
+                assert_eq!((path[0], path[path.len() - 1]), (start, end));
+                assert_eq!(path_cost(&grid, &path, movement), cost);
+                assert!(cost >= optimal_cost);
+            }
+        }
+    }
+}
+
+#[test]
+fn hierarchical_path_update_tile() {
+    let mut grid = parse_map(&["..........", "..........", "..........", "..........", "..........", ".........."]);
+    let movement = Movement::Orthogonal;
+    let mut pathfinder = HierarchicalPathfinder::new(&grid, 4, movement, &terrain_cost);
+    let start = Position::new(0, 0);
+    let end = Position::new(9, 5);
+    assert_eq!(pathfinder.get_path(&grid, start, end, &terrain_cost).unwrap().1, 14);
+
+    for y in 0..6 {
+        let wall = Position::new(5, y);
+        grid.set(wall, '#').unwrap();
+        pathfinder.update_tile(&grid, wall, &terrain_cost);
+    }
+    assert!(pathfinder.get_path(&grid, start, end, &terrain_cost).is_none());
+
+    let hole = Position::new(5, 3);
+    grid.set(hole, '.').unwrap();
+    pathfinder.update_tile(&grid, hole, &terrain_cost);
+    let (path, cost) = pathfinder.get_path(&grid, start, end, &terrain_cost).unwrap();
+    assert!(path.contains(&hole));
+    assert_eq!(cost, 14);
+}
+
+#[test]
+fn incremental_path_repairs_after_changes() {
+    let movements = [Movement::Orthogonal, Movement::Diagonal { corner_cutting: false }, Movement::Octile { corner_cutting: true }];
+    for (seed, movement) in movements.into_iter().enumerate() {
+        let mut grid = generate_map(20, 15, seed as u64 + 10);
+        let start = Position::new(0, 0);
+        let goal = Position::new(19, 14);
+        grid.set(start, '.').unwrap();
+        grid.set(goal, '.').unwrap();
+        let mut planner = IncrementalPathfinder::new(&grid, start, goal, movement, &terrain_cost);
+
+        for step in 0..40 {
+            let changed = Position::new((step * 7) % 20, (step * 11) % 15);
+            if changed != start && changed != goal {
+                let tile = if grid[changed] == '#' { '.' } else { '#' };
+                grid.set(changed, tile).unwrap();
+                planner.update_tile(&grid, changed, &terrain_cost);
+            }
+
+            let expected = get_shortest_weighted_path(&grid, start, goal, movement, &terrain_cost);
+            let result = planner.get_path(&grid, &terrain_cost);
+            assert_eq!(result.as_ref().map(|(_, cost)| *cost), expected.map(|(_, cost)| cost), "{movement:?} step {step}");
+            if let Some((path, cost)) = result {
+                assert_eq!((path[0], path[path.len() - 1]), (start, goal));
+                assert_eq!(path_cost(&grid, &path, movement), cost);
+            }
+        }
+    }
+}
+
+#[test]
+fn incremental_path_moving_start() {
+    let mut grid = parse_map(&["......", "......", "......", "......"]);
+    let movement = Movement::Orthogonal;
+    let goal = Position::new(5, 3);
+    let mut planner = IncrementalPathfinder::new(&grid, Position::new(0, 0), goal, movement, &terrain_cost);
+    assert_eq!(planner.cost(), Some(8));
+
+    let (path, _) = planner.get_path(&grid, &terrain_cost).unwrap();
+    planner.set_start(&grid, path[2], &terrain_cost);
+    assert_eq!(planner.cost(), Some(6));
+
+    for x in 0..5 {
+        let wall = Position::new(x, 2);
+        grid.set(wall, '#').unwrap();
+        planner.update_tile(&grid, wall, &terrain_cost);
+    }
+    let expected = get_shortest_weighted_path(&grid, path[2], goal, movement, &terrain_cost).unwrap();
+    assert_eq!(planner.get_path(&grid, &terrain_cost).unwrap().1, expected.1);
+
+    grid.set(Position::new(5, 2), '#').unwrap();
+    planner.update_tile(&grid, Position::new(5, 2), &terrain_cost);
+    assert!(planner.get_path(&grid, &terrain_cost).is_none());
+}
+
+#[test]
+fn jump_point_path_matches_astar() {
+    let movement = Movement::Octile { corner_cutting: false };
+    let uniform_cost = |&tile: &char| (tile != '#').then_some(1);
+    for seed in 0..4 {
+        let grid = generate_map(25, 20, seed + 20);
+        for (start_index, end_index) in [(0, 499), (26, 300), (100, 107), (260, 140), (480, 20), (333, 334)] {
+            let start = Position::new(start_index % 25, start_index / 25);
+            let end = Position::new(end_index % 25, end_index / 25);
+            let expected = get_shortest_weighted_path(&grid, start, end, movement, &uniform_cost);
+            let result = get_jump_point_path(&grid, start, end, &|&tile| tile != '#');
+
+            assert_eq!(result.as_ref().map(|(_, cost)| *cost), expected.map(|(_, cost)| cost), "seed {seed} {start:?} {end:?}");
+            if let Some((path, cost)) = result {
+                assert_eq!((path[0], path[path.len() - 1]), (start, end));
+                let path_cost: i64 = path.windows(2).map(|step| movement.step_cost(step[1] - step[0])).sum();
+                assert_eq!(path_cost, cost);
+                assert!(path.iter().skip(1).all(|&position| grid[position] != '#'));
+            }
+        }
+    }
+}
+
+#[test]
+fn jump_point_path_no_corner_cutting() {
+    let grid = parse_map(&[".#.", "...", "#.."]);
+    let (path, cost) = get_jump_point_path(&grid, Position::new(0, 0), Position::new(2, 0), &|&tile| tile != '#').unwrap();
+    assert_eq!(path.len(), 5);
+    assert_eq!(cost, 4 * OCTILE_STRAIGHT_COST);
+    assert!(get_jump_point_path(&parse_map(&[".#", "#."]), Position::new(0, 0), Position::new(1, 1), &|&tile| tile != '#').is_none());
+}
+
+#[test]
+fn wrapped_path_crosses_seam() {
+    let rows = ["...#...", "...#...", "...#..."];
+    let start = Position::new(0, 1);
+    let end = Position::new(6, 1);
+    assert_eq!(get_shortest_path(&parse_map(&rows), start, end, &|&tile| tile != '#'), None);
+
+    let grid = parse_map(&rows).with_topology(Topology::WrapHorizontal);
+    let (path, cost) = get_shortest_path(&grid, start, end, &|&tile| tile != '#').unwrap();
+    assert_eq!(path, [start, end]);
+    assert_eq!(cost, 1);
+
+    let distances = get_distance_map(&grid, &[end], &|&tile| tile != '#');
+    assert_eq!(distances[Position::new(2, 0)], Some(4));
+    let flow_field = get_flow_field(&grid, &distances, Movement::Orthogonal, &|&tile| (tile != '#').then_some(1));
+    assert_eq!(flow_field[start], Some(Direction::Left));
+
+    let reachable = get_reachable_tiles(&grid, end, 1, Movement::Orthogonal, &terrain_cost);
+    assert_eq!(reachable.path_to(start), Some(vec![end, start]));
+}
+
+#[test]
+fn wrapped_paths_match_astar() {
+    let movements = [Movement::Orthogonal, Movement::Diagonal { corner_cutting: true }, Movement::Octile { corner_cutting: false }];
+    for (seed, movement) in movements.into_iter().enumerate() {
+        let grid = generate_map(18, 14, seed as u64 + 30).with_topology(Topology::Torus);
+        let pathfinder = HierarchicalPathfinder::new(&grid, 5, movement, &terrain_cost);
+
+        for (start, end) in [((0, 0), (17, 13)), ((1, 7), (16, 7)), ((9, 0), (9, 13)), ((3, 2), (8, 9))] {
+            let (start, end) = (Position::new(start.0, start.1), Position::new(end.0, end.1));
+            let expected = get_shortest_weighted_path(&grid, start, end, movement, &terrain_cost).map(|(_, cost)| cost);
+            let distances = get_weighted_distance_map(&grid, &[end], movement, &terrain_cost);
+            let planner = IncrementalPathfinder::new(&grid, start, end, movement, &terrain_cost);
+
+            assert_eq!(distances[start].map(i64::from), expected, "{movement:?} {start:?} {end:?}");
+            assert_eq!(planner.cost(), expected, "{movement:?} {start:?} {end:?}");
+            let result = pathfinder.get_path(&grid, start, end, &terrain_cost);
+            assert_eq!(result.is_some(), expected.is_some(), "{movement:?} {start:?} {end:?}");
+            if let (Some((path, cost)), Some(optimal_cost)) = (result, expected) {
+                assert_eq!((path[0], path[path.len() - 1]), (start, end));
+                assert_eq!(path_cost(&grid, &path, movement), cost);
+                assert!(cost >= optimal_cost);
+            }
+        }
+    }
+}
+
+#[test]
+fn wrapped_jump_point_path_matches_astar() {
+    let movement = Movement::Octile { corner_cutting: false };
+    let uniform_cost = |&tile: &char| (tile != '#').then_some(1);
+    for (seed, topology) in [Topology::WrapHorizontal, Topology::WrapVertical, Topology::Torus].into_iter().enumerate() {
+        let grid = generate_map(16, 12, seed as u64 + 40).with_topology(topology);
+        for (start, end) in [((0, 0), (15, 11)), ((1, 5), (14, 6)), ((7, 0), (7, 11)), ((2, 2), (9, 8))] {
+            let (start, end) = (Position::new(start.0, start.1), Position::new(end.0, end.1));
+            let expected = get_shortest_weighted_path(&grid, start, end, movement, &uniform_cost);
+            let result = get_jump_point_path(&grid, start, end, &|&tile| tile != '#');
+
+            assert_eq!(result.as_ref().map(|(_, cost)| *cost), expected.map(|(_, cost)| cost), "{topology:?} {start:?} {end:?}");
+            if let Some((path, cost)) = result {
+                assert_eq!((path[0], path[path.len() - 1]), (start, end));
+                let path_cost: i64 = path.windows(2).map(|step| movement.step_cost(topology.offset(step[0], step[1], grid.size()))).sum();
+                assert_eq!(path_cost, cost);
+            }
+        }
+    }
+
+    // an open torus has no walls to stop a jump, it must not run around forever
+    let open = Grid::new(5, 4, '.').with_topology(Topology::Torus);
+    let (_, cost) = get_jump_point_path(&open, Position::new(0, 0), Position::new(4, 3), &|&tile| tile != '#').unwrap();
+    assert_eq!(cost, OCTILE_DIAGONAL_COST);
+}
@@ -1,5 +1,6 @@
+use test_case::test_case;
 use xs_games_rs::{
-    dim2::grid::{Position, RectSize, ScreenTranslation},
+    dim2::grid::{Position, Projection, RectSize, ScreenTranslation},
     ScreenView,
 };
 
@@ -50,3 +51,131 @@ fn create_test_translation_negative() -> ScreenTranslation {
     let logical_size = RectSize { width: 10, height: 10 };
     ScreenTranslation::new(screen_view, logical_size)
 }
+
+fn create_projected_translation(projection: Projection) -> ScreenTranslation {
+    let screen_view = ScreenView {
+        offset_x: 20.,
+        offset_y: -10.,
+        width: 300.,
+        height: 200.,
+    };
+    ScreenTranslation::with_projection(screen_view, RectSize::new(5, 3), projection)
+}
+
+#[test]
+fn dimetric_diamond_hit_test() {
+    let screen_view = ScreenView {
+        offset_x: 0.,
+        offset_y: 0.,
+        width: 100.,
+        height: 50.,
+    };
+    let translation = ScreenTranslation::with_projection(screen_view, RectSize::new(2, 2), Projection::Dimetric);
+    assert_eq!((translation.tile_width(), translation.tile_height()), (50., 25.));
+    assert_eq!(translation.block_center_to_screen_position(0, 0), (50., 12.5));
+    assert_eq!(translation.block_center_to_screen_position(1, 0), (75., 25.));
+    assert_eq!(translation.block_center_to_screen_position(0, 1), (25., 25.));
+
+    assert_eq!(translation.get_grid_position(50., 1.), Some(Position::new(0, 0)));
+    assert_eq!(translation.get_grid_position(74., 12.5), Some(Position::new(0, 0)));
+    assert_eq!(translation.get_grid_position(62., 20.), Some(Position::new(1, 0)));
+    // right of the top tile, but outside of its diamond
+    assert_eq!(translation.get_grid_position(76., 12.5), None);
+    assert_eq!(translation.get_grid_position(5., 5.), None);
+}
+
+#[test_case(Projection::Isometric)]
+#[test_case(Projection::Dimetric)]
+#[test_case(Projection::Staggered)]
+fn projected_round_trip(projection: Projection) {
+    let translation = create_projected_translation(projection);
+    let ratio = translation.tile_height() / translation.tile_width();
+    assert!((ratio - projection.tile_height_ratio().unwrap()).abs() < 1e-5);
+
+    for position in RectSize::new(5, 3).iter() {
+        let (x, y) = translation.block_center_to_screen_position(position.x, position.y);
+        assert_eq!(translation.get_grid_position(x, y), Some(position));
+
+        // slightly inside of the four corners of the diamond
+        let (half_width, half_height) = (translation.tile_width() / 2. * 0.95, translation.tile_height() / 2. * 0.95);
+        for (corner_x, corner_y) in [(x - half_width, y), (x + half_width, y), (x, y - half_height), (x, y + half_height)] {
+            assert_eq!(translation.get_grid_position(corner_x, corner_y), Some(position), "{projection:?} {position:?}");
+        }
+    }
+}
+
+#[test_case(Projection::Orthogonal)]
+#[test_case(Projection::Isometric)]
+#[test_case(Projection::Staggered)]
+fn draw_order_back_to_front(projection: Projection) {
+    let translation = create_projected_translation(projection);
+    let order: Vec<_> = translation.iter_draw_order().collect();
+    assert_eq!(order.len(), 15);
+
+    for (index, position) in order.iter().enumerate() {
+        let (_, y) = translation.block_center_to_screen_position(position.x, position.y);
+        for later in &order[index + 1..] {
+            let (_, later_y) = translation.block_center_to_screen_position(later.x, later.y);
+            assert!(later_y >= y, "{later:?} is drawn after {position:?}");
+        }
+    }
+}
+
+#[test]
+fn isometric_draw_order() {
+    let screen_view = ScreenView {
+        offset_x: 0.,
+        offset_y: 0.,
+        width: 100.,
+        height: 100.,
+    };
+    let translation = ScreenTranslation::with_projection(screen_view, RectSize::new(3, 2), Projection::Isometric);
+    let order: Vec<_> = translation.iter_draw_order().collect();
+    assert_eq!(order, [(0, 0), (0, 1), (1, 0), (1, 1), (2, 0), (2, 1)].map(Position::from));
+}
+
+#[test]
+fn visible_range_of_large_grid() {
+    // 1000x1000 tiles of 10 pixels, the viewport shows a small part of it
+    let translation = ScreenTranslation::new(ScreenView { offset_x: 0., offset_y: 0., width: 10000., height: 10000. }, RectSize::new(1000, 1000));
+    let viewport = ScreenView { offset_x: 105., offset_y: 200., width: 100., height: 50. };
+    assert_eq!(translation.visible_range(viewport, 0), Some((Position::new(10, 20), RectSize::new(11, 5))));
+    assert_eq!(translation.visible_range(viewport, 2), Some((Position::new(8, 18), RectSize::new(15, 9))));
+    assert_eq!(translation.iter_visible_positions(viewport, 0).count(), 55);
+
+    // clamped at the edges of the grid
+    let corner = ScreenView { offset_x: -50., offset_y: 9950., width: 100., height: 100. };
+    assert_eq!(translation.visible_range(corner, 1), Some((Position::new(0, 994), RectSize::new(6, 6))));
+    let outside = ScreenView { offset_x: -200., offset_y: 0., width: 100., height: 100. };
+    assert_eq!(translation.visible_range(outside, 0), None);
+    assert_eq!(translation.iter_visible_positions(outside, 0).count(), 0);
+}
+
+#[test_case(Projection::Orthogonal)]
+#[test_case(Projection::Isometric)]
+#[test_case(Projection::Dimetric)]
+#[test_case(Projection::Staggered)]
+fn visible_positions_match_brute_force(projection: Projection) {
+    let translation = ScreenTranslation::with_projection(ScreenView { offset_x: 0., offset_y: 0., width: 800., height: 600. }, RectSize::new(20, 15), projection);
+    let viewport = ScreenView { offset_x: 230., offset_y: 170., width: 150., height: 90. };
+    for margin in [0, 1] {
+        let (margin_x, margin_y) = (margin as f32 * translation.tile_width(), margin as f32 * translation.tile_height());
+        let (half_width, half_height) = (translation.tile_width() / 2., translation.tile_height() / 2.);
+        let expected: Vec<Position> = translation
+            .iter_draw_order()
+            .filter(|position| {
+                let (x, y) = translation.block_center_to_screen_position(position.x, position.y);
+                x + half_width > viewport.offset_x - margin_x
+                    && x - half_width < viewport.offset_x + viewport.width + margin_x
+                    && y + half_height > viewport.offset_y - margin_y
+                    && y - half_height < viewport.offset_y + viewport.height + margin_y
+            })
+            .collect();
+        assert!(!expected.is_empty());
+        assert_eq!(translation.iter_visible_positions(viewport, margin).collect::<Vec<_>>(), expected);
+
+        let (offset, size) = translation.visible_range(viewport, margin).unwrap();
+        assert!(expected.iter().all(|position| (offset.x..offset.x + size.width as i64).contains(&position.x)
+            && (offset.y..offset.y + size.height as i64).contains(&position.y)));
+    }
+}
@@ -0,0 +1,118 @@
+mod common;
+
+use common::parse_map;
+use xs_games_rs::dim2::grid::{
+    pathing::{
+        get_jump_point_path, get_reachable_tiles, get_shortest_path, get_shortest_weighted_path, path_exists, Movement, OCTILE_STRAIGHT_COST,
+        UNBOUNDED_SEARCH_MARGIN,
+    },
+    patterns::{get_grid_values_from_pattern, square_pattern, PatternPositions},
+    GridError, GridRead, Position, RectSize, SparseGrid,
+};
+
+#[test]
+fn stores_only_non_default_tiles() {
+    let mut grid = SparseGrid::with_bounds(RectSize::new(1000, 1000), 0);
+    assert_eq!(grid.get(Position::new(999, 999)), Some(0));
+    assert_eq!(grid.get(Position::new(1000, 0)), None);
+
+    grid.set(Position::new(5, 5), 3).unwrap();
+    grid.set(Position::new(6, 5), 0).unwrap();
+    assert_eq!(grid.stored_count(), 1);
+    assert_eq!(
+        grid.set(Position::new(-1, 5), 1),
+        Err(GridError::OutOfBounds {
+            position: Position::new(-1, 5),
+            size: RectSize::new(1000, 1000)
+        })
+    );
+
+    *grid.get_mut(Position::new(7, 7)).unwrap() += 0;
+    assert_eq!(grid.stored_count(), 2);
+    grid.prune();
+    assert_eq!(grid.stored_count(), 1);
+
+    grid.set(Position::new(5, 5), 0).unwrap();
+    assert_eq!(grid.stored_count(), 0);
+}
+
+#[test]
+fn unbounded() {
+    let mut grid = SparseGrid::new('.');
+    grid.set(Position::new(-1_000_000, 42), '#').unwrap();
+    assert_eq!(grid.get(Position::new(-1_000_000, 42)), Some('#'));
+    assert_eq!(grid.get(Position::new(i64::MAX, i64::MIN)), Some('.'));
+    assert_eq!(GridRead::bounds(&grid), None);
+    assert_eq!(grid.iter_with_position().collect::<Vec<_>>(), [(Position::new(-1_000_000, 42), &'#')]);
+}
+
+#[test]
+fn pathing_matches_dense_grid() {
+    let rows = ["......", ".####.", "....#.", "###.#.", "......"];
+    let dense = parse_map(&rows);
+    let mut sparse = SparseGrid::with_bounds(dense.size(), '.');
+    for (position, &tile) in dense.iter_with_position() {
+        sparse.set(position, tile).unwrap();
+    }
+
+    let is_pathable = |&tile: &char| tile != '#';
+    let start = Position::new(0, 2);
+    let end = Position::new(0, 4);
+    assert_eq!(get_shortest_path(&sparse, start, end, &is_pathable), get_shortest_path(&dense, start, end, &is_pathable));
+    assert_eq!(
+        get_jump_point_path(&sparse, start, end, &is_pathable).map(|(_, cost)| cost),
+        get_jump_point_path(&dense, start, end, &is_pathable).map(|(_, cost)| cost)
+    );
+
+    let center = Position::new(4, 1);
+    assert_eq!(
+        get_grid_values_from_pattern(&sparse, center, true, square_pattern(1).as_ref()),
+        get_grid_values_from_pattern(&dense, center, true, square_pattern(1).as_ref())
+    );
+}
+
+#[test]
+fn pathing_on_unbounded_grid() {
+    let mut grid = SparseGrid::new(false);
+    for y in -10..=10 {
+        grid.set(Position::new(0, y), true).unwrap();
+    }
+    let is_pathable = |&blocked: &bool| !blocked;
+    let start = Position::new(-3, 0);
+    let end = Position::new(3, 0);
+
+    let (path, cost) = get_shortest_path(&grid, start, end, &is_pathable).unwrap();
+    assert_eq!(cost, 28);
+    assert!(path.contains(&Position::new(0, 11)) || path.contains(&Position::new(0, -11)));
+
+    let tile_cost = |&blocked: &bool| (!blocked).then_some(1);
+    let movement = Movement::Octile { corner_cutting: false };
+    let expected = get_shortest_weighted_path(&grid, start, end, movement, &tile_cost).unwrap().1;
+    let (_, cost) = get_jump_point_path(&grid, start, Position::new(200, 100), &is_pathable).unwrap();
+    assert!(cost >= 200 * OCTILE_STRAIGHT_COST);
+    assert_eq!(get_jump_point_path(&grid, start, end, &is_pathable).unwrap().1, expected);
+
+    assert_eq!(get_reachable_tiles(&grid, start, 2, Movement::Orthogonal, &tile_cost).len(), 13);
+}
+
+#[test]
+fn unreachable_end_on_unbounded_grid() {
+    // the end is walled in, the open space around it is endless
+    let mut grid = SparseGrid::new(false);
+    let end = Position::new(5, 5);
+    for offset in square_pattern(1).get_pattern_positions(end, false).iter() {
+        grid.set(*offset, true).unwrap();
+    }
+    let is_pathable = |&blocked: &bool| !blocked;
+    let start = Position::ZERO;
+
+    assert!(!path_exists(&grid, start, end, &is_pathable));
+    assert!(get_jump_point_path(&grid, start, end, &is_pathable).is_none());
+
+    // an opening far outside of the search area is not found
+    let mut wall = SparseGrid::new(false);
+    for y in -UNBOUNDED_SEARCH_MARGIN - 10..=UNBOUNDED_SEARCH_MARGIN + 10 {
+        wall.set(Position::new(0, y), true).unwrap();
+    }
+    assert!(get_shortest_path(&wall, Position::new(-1, 0), Position::new(1, 0), &is_pathable).is_none());
+}